@@ -1,15 +1,53 @@
 pub mod client_table;
+pub mod columns;
+pub mod command_line;
+pub mod history;
+pub mod input_history;
+pub mod json;
+pub mod minimap;
+pub mod mouse;
+pub mod scrolling;
+pub mod text_input;
 pub mod workspace_table;
 
-use anyhow::{Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget, DefaultTerminal, Frame};
+use std::rc::Rc;
 
-use crate::screen::{table_screen::TableScreen, Screen, ScreenEvent};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Widget, WidgetRef},
+    DefaultTerminal, Frame,
+};
+
+use crate::config::Config;
+use crate::hypr::{self, FocusState, Hypr};
+use crate::screen::{
+    console_screen::ConsoleScreen, help_screen::HelpScreen, history_screen::HistoryScreen,
+    mru_screen::MruScreen, table_screen::TableScreen, Screen, ScreenEvent,
+};
+use command_line::{CommandLine, CommandLineEvent};
+use history::History;
+
+/// The screen wmt opens on.
+pub enum StartScreen {
+    Tables,
+    Mru,
+}
 
 pub struct App {
     exit: bool,
     screens: Vec<Box<dyn Screen>>,
+    focus_state: FocusState,
+    peeked: bool,
+    history: History,
+    /// The `:` prompt, while it is open.
+    command_line: Option<CommandLine>,
+    /// The outcome of the last command, until the next key.
+    status: Option<Result<String>>,
 }
 
 impl App {
@@ -21,13 +59,27 @@ impl App {
         Ok(())
     }
 
-    pub fn new() -> Result<App> {
-        let table_screen = TableScreen::new().context("Creating table screen")?;
-        let mut screens: Vec<Box<dyn Screen>> = Vec::new();
-        screens.push(Box::new(table_screen));
+    pub fn new(start_screen: StartScreen) -> Result<App> {
+        let config = Rc::new(Config::load().context("Loading config")?);
+        let hypr = Hypr::without_own_window().context("Connecting to Hyprland")?;
+        let focus_state = FocusState::capture(&hypr);
+        let screen: Box<dyn Screen> = match start_screen {
+            StartScreen::Tables => Box::new(TableScreen::new(config, hypr)),
+            StartScreen::Mru => Box::new(MruScreen::with_hypr(
+                hypr,
+                config.client_columns.clone(),
+                true,
+            )),
+        };
+        let screens: Vec<Box<dyn Screen>> = vec![screen];
         Ok(App {
             exit: false,
             screens,
+            focus_state,
+            peeked: false,
+            history: History::default(),
+            command_line: None,
+            status: None,
         })
     }
 
@@ -37,18 +89,41 @@ impl App {
 
     fn handle_events(&mut self) -> Result<()> {
         match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Release => {
+                self.handle_key_release(key_event)
             }
+            Event::Key(key_event) => self.handle_key_event(key_event),
+            Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
             _ => {}
         };
         Ok(())
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        self.status = None;
+        if let Some(command_line) = self.command_line.as_mut() {
+            match command_line.handle_key_event(key_event) {
+                Some(CommandLineEvent::Run(command)) => {
+                    self.command_line = None;
+                    self.run_command(&command);
+                }
+                Some(CommandLineEvent::Cancel) => self.command_line = None,
+                None => {}
+            }
+            return;
+        }
+
+        let captures_input = self
+            .screens
+            .last()
+            .is_some_and(|widget| widget.captures_input());
         match key_event.code {
-            KeyCode::Char('q') => self.exit(),
-            KeyCode::Char('Q') => self.exit(),
+            KeyCode::Char('q') if !captures_input => self.exit(),
+            KeyCode::Char('Q') if !captures_input => self.exit(),
+            KeyCode::Char(':') if !captures_input => match CommandLine::new() {
+                Ok(command_line) => self.command_line = Some(command_line),
+                Err(err) => self.status = Some(Err(err)),
+            },
 
             _ => {
                 if let Some(widget) = self.screens.last_mut() {
@@ -60,6 +135,27 @@ impl App {
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        if let Some(widget) = self.screens.last_mut() {
+            if let Some(screen_event) = widget.handle_mouse_event(mouse_event) {
+                self.handle_screen_event(screen_event);
+            }
+        }
+    }
+
+    /// Releases are left alone while typing a command, so they don't confirm
+    /// the alt-tab list underneath.
+    fn handle_key_release(&mut self, key_event: KeyEvent) {
+        if self.command_line.is_some() {
+            return;
+        }
+        if let Some(widget) = self.screens.last_mut() {
+            if let Some(screen_event) = widget.handle_key_release(key_event) {
+                self.handle_screen_event(screen_event);
+            }
+        }
+    }
+
     fn handle_screen_event(&mut self, screen_event: ScreenEvent) {
         match screen_event {
             ScreenEvent::Close => self.exit = true,
@@ -67,30 +163,141 @@ impl App {
                 self.screens.push(screen);
             }
             ScreenEvent::PopScreen => {
-                if let None = self.screens.pop() {
-                    self.exit = true;
+                self.screens.pop();
+                if self.screens.is_empty() {
+                    self.exit();
                 }
             }
             ScreenEvent::PopAndRefresh => {
-                if let None = self.screens.pop() {
-                    self.exit = true;
+                self.screens.pop();
+                if self.screens.is_empty() {
+                    self.exit();
                 }
-                if let Some(widget) = self.screens.last_mut() {
-                    widget.refresh();
+                self.refresh_screen();
+            }
+            ScreenEvent::PopAndRecord(operation) => {
+                self.history.record(operation);
+                self.handle_screen_event(ScreenEvent::PopAndRefresh);
+            }
+            ScreenEvent::Record(operation) => {
+                self.history.record(operation);
+                self.refresh_screen();
+            }
+            ScreenEvent::Undo(count) => self.undo(count),
+            ScreenEvent::PopAndUndo(count) => {
+                self.screens.pop();
+                self.undo(count);
+            }
+            ScreenEvent::ShowHistory => {
+                let history_screen = HistoryScreen::new(self.history.descriptions());
+                self.screens.push(Box::new(history_screen));
+            }
+            ScreenEvent::Error(err) => self.status = Some(Err(err)),
+            ScreenEvent::Peek(client_address) => {
+                if self.focus_state.peek(&client_address).is_ok() {
+                    self.peeked = true;
                 }
             }
         }
     }
 
+    /// Reverts `count` operations, saying in the status line if one failed.
+    fn undo(&mut self, count: usize) {
+        if let Err(err) = self.history.undo(count) {
+            self.status = Some(Err(err));
+        }
+        self.refresh_screen();
+    }
+
+    /// Runs `command` and saves it to the history. Failing to save only
+    /// shows when the command itself didn't fail.
+    fn run_command(&mut self, command: &str) {
+        // Actions like undo may have set the status themselves
+        if let Some(status) = self.execute_command(command).transpose() {
+            self.status = Some(status);
+        }
+        if let Err(err) = command_line::record(command) {
+            if !matches!(self.status, Some(Err(_))) {
+                self.status = Some(Err(err.context("Saving the command history")));
+            }
+        }
+    }
+
+    /// Runs one of wmt's [`command_line::ACTIONS`], or else sends `command`
+    /// to Hyprland as a dispatcher. Returns the reply to show, if any.
+    fn execute_command(&mut self, command: &str) -> Result<Option<String>> {
+        let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+        let screen_event = match name {
+            "quit" => ScreenEvent::Close,
+            "back" => ScreenEvent::PopScreen,
+            "console" => ScreenEvent::AddScreen(Box::new(ConsoleScreen::new())),
+            "help" => ScreenEvent::AddScreen(Box::new(HelpScreen::new())),
+            "history" => ScreenEvent::ShowHistory,
+            "undo" => match args.trim() {
+                "" => ScreenEvent::Undo(1),
+                count => ScreenEvent::Undo(count.parse().context("Undo takes a count")?),
+            },
+            "refresh" => {
+                self.refresh_screen();
+                return Ok(None);
+            }
+            _ => {
+                let selection = self
+                    .screens
+                    .last()
+                    .map(|screen| screen.selection())
+                    .unwrap_or_default();
+                let dispatch = selection.fill(command)?;
+                let reply = hypr::send_cmd(&format!("dispatch {}", dispatch))?;
+                if reply.trim() != "ok" {
+                    bail!("{}", reply.trim());
+                }
+                self.refresh_screen();
+                return Ok(Some(format!("{}: ok", dispatch)));
+            }
+        };
+        match screen_event {
+            // Quitting from here undoes peeking, like Q does
+            ScreenEvent::Close => self.exit(),
+            screen_event => self.handle_screen_event(screen_event),
+        }
+
+        Ok(None)
+    }
+
+    fn refresh_screen(&mut self) {
+        if let Some(widget) = self.screens.last_mut() {
+            widget.refresh();
+        }
+    }
+
+    /// Leaves without confirming anything, so undo any peeking first.
     fn exit(&mut self) {
+        if self.peeked {
+            let _ = self.focus_state.restore();
+        }
         self.exit = true;
     }
 }
 
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let [area, bottom_line] = if self.command_line.is_some() || self.status.is_some() {
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area)
+        } else {
+            [area, Rect::default()]
+        };
         if let Some(widget) = self.screens.last() {
             widget.render_ref(area, buf);
         }
+
+        if let Some(command_line) = &self.command_line {
+            command_line.render_ref(bottom_line, buf);
+        }
+        match &self.status {
+            Some(Ok(reply)) => Line::from(reply.as_str()).render(bottom_line, buf),
+            Some(Err(err)) => Line::from(format!("{:#}", err).red()).render(bottom_line, buf),
+            None => {}
+        }
     }
 }
@@ -1,93 +1,262 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::Rect,
     style::{Style, Stylize},
-    widgets::{Row, StatefulWidget, Table, TableState, WidgetRef},
+    widgets::{Row, Table, TableState, WidgetRef},
 };
 
-use crate::hypr::Client;
+use crate::{app::command_line::Selection, hypr::Client};
+
+use super::{
+    columns::{ClientColumn, Columns},
+    scrolling::{self, Motion, Viewport},
+};
+
+pub const ACTIVE_MARKER: &str = "●";
+
+/// A row of the table.
+enum Entry {
+    /// Index into the table's clients.
+    Client(usize),
+    /// The header of a group, with the addresses of the clients under it.
+    Header(Vec<String>),
+}
 
 pub struct ClientTable<'a> {
     state: TableState,
     table: Table<'a>,
     clients: Vec<Client>,
+    entries: Vec<Entry>,
     len: usize,
+    viewport: Viewport,
+}
+
+/// Clients listed together under a header, in a table built with
+/// [`ClientTable::grouped`].
+pub struct ClientGroup {
+    pub title: String,
+    pub clients: Vec<Client>,
 }
 
 impl<'a> ClientTable<'_> {
-    pub fn new(clients: Vec<Client>) -> ClientTable<'a> {
-        let state = TableState::default().with_selected(Some(0));
-        let widths = [
-            Constraint::Max(15),
-            Constraint::Min(30),
-            Constraint::Max(10),
-        ];
-        let rows: Vec<Row> = clients
-            .iter()
-            .map(|client| {
-                Row::new(vec![
-                    client.class.clone(),
-                    client.title.clone(),
-                    client.workspace.name.clone(),
-                ])
+    /// `active_address` is the focused client, which gets marked in the table.
+    /// Clients are sorted as `columns` says.
+    pub fn new(
+        mut clients: Vec<Client>,
+        active_address: Option<String>,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        columns.sort(&mut clients);
+        let prefixes = vec![""; clients.len()];
+        Self::build(vec![(None, clients, prefixes)], active_address, columns)
+    }
+
+    /// Like [`ClientTable::new`], with the members of each window group
+    /// nested under the first window of the group.
+    pub fn nested(
+        mut clients: Vec<Client>,
+        active_address: Option<String>,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        columns.sort(&mut clients);
+        let (clients, prefixes) = nest_groups(clients);
+        Self::build(vec![(None, clients, prefixes)], active_address, columns)
+    }
+
+    /// Like [`ClientTable::nested`], with each of `groups` under a header
+    /// row that shows how many clients it has.
+    pub fn grouped(
+        groups: Vec<ClientGroup>,
+        active_address: Option<String>,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        let sections = groups
+            .into_iter()
+            .map(|mut group| {
+                columns.sort(&mut group.clients);
+                let (clients, prefixes) = nest_groups(group.clients);
+                (Some(group.title), clients, prefixes)
             })
             .collect();
-        let table = Table::new(rows, widths)
-            .header(Row::new(vec!["Class", "Title", "Workspace"]).bold())
+        Self::build(sections, active_address, columns)
+    }
+
+    /// Each section is an optional header title, then its clients with the
+    /// prefix to draw in front of their first column.
+    fn build(
+        sections: Vec<(Option<String>, Vec<Client>, Vec<&str>)>,
+        active_address: Option<String>,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        let state = TableState::default().with_selected(Some(0));
+        let mut clients = Vec::new();
+        let mut entries = Vec::new();
+        let mut rows = Vec::new();
+        for (title, section, prefixes) in sections {
+            if let Some(title) = title {
+                let addresses = section.iter().map(|c| c.address.clone()).collect();
+                entries.push(Entry::Header(addresses));
+                let title = format!("{} ({})", title, section.len());
+                rows.push(Row::new(vec![String::new(), title]).bold());
+            }
+            for (client, prefix) in section.into_iter().zip(prefixes) {
+                let marker = if Some(&client.address) == active_address.as_ref() {
+                    ACTIVE_MARKER
+                } else {
+                    ""
+                };
+                let mut cells = vec![marker.to_string()];
+                cells.extend(columns.cells(&client));
+                if let Some(first) = cells.get_mut(1) {
+                    first.insert_str(0, prefix);
+                }
+                rows.push(Row::new(cells));
+                entries.push(Entry::Client(clients.len()));
+                clients.push(client);
+            }
+        }
+        let table = Table::new(rows, columns.widths())
+            .header(columns.header())
             .row_highlight_style(Style::new().reversed());
-        let len = clients.len();
+        let len = entries.len();
 
         ClientTable {
             state,
             table,
             clients,
+            entries,
             len,
+            viewport: Viewport::default(),
         }
     }
 
-    pub fn move_down(&mut self) {
-        if let Some(i) = self.state.selected() {
-            if i < self.len - 1 {
-                self.state.select_next();
-            }
+    pub fn move_cursor(&mut self, motion: Motion) {
+        scrolling::move_cursor(&mut self.state, motion, self.len, self.viewport.page());
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.len {
+            self.state.select(Some(index));
         }
     }
 
-    pub fn move_up(&mut self) {
-        if let Some(i) = self.state.selected() {
-            if i > 0 {
-                self.state.select_previous();
-            }
+    pub fn selected_row(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    /// Selects the row at `column`, `row` on the terminal, returning false
+    /// if there is none there.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let Some(index) = self.viewport.row_at(column, row, self.len) else {
+            return false;
+        };
+        self.select(index);
+
+        true
+    }
+
+    pub fn select_client(&mut self, client_address: &str) {
+        if let Some(index) = self.entries.iter().position(|entry| match entry {
+            Entry::Client(i) => self.clients[*i].address == client_address,
+            Entry::Header(_) => false,
+        }) {
+            self.select(index);
         }
     }
 
-    pub fn selected_workspace(&self) -> (Option<u32>, Option<String>) {
-        if let Some(index) = self.state.selected() {
-            if let Some(client) = self.clients.get(index) {
-                return (Some(client.workspace.id), Some(client.address.clone()));
-            } else {
-                return (None, None);
-            }
+    /// The target of the selected client's workspace, see
+    /// [`crate::hypr::ClientWorkspace::target`], and the client's address.
+    pub fn selected_workspace(&self) -> (Option<String>, Option<String>) {
+        if let Some(client) = self.selected() {
+            (
+                Some(client.workspace.target()),
+                Some(client.address.clone()),
+            )
         } else {
-            return (None, None);
+            (None, None)
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Client> {
+        match self.entries.get(self.state.selected()?)? {
+            Entry::Client(index) => self.clients.get(*index),
+            Entry::Header(_) => None,
         }
     }
 
     pub fn selected_client(&self) -> Option<String> {
-        if let Some(index) = self.state.selected() {
-            if let Some(client) = self.clients.get(index) {
-                return Some(client.address.clone());
-            } else {
-                return None;
-            }
-        } else {
-            return None;
+        Some(self.selected()?.address.clone())
+    }
+
+    /// The selected client, for the placeholders of `:` commands.
+    pub fn selection(&self) -> Selection {
+        let Some(client) = self.selected() else {
+            return Selection::default();
+        };
+        Selection {
+            address: Some(client.address.clone()),
+            workspace: Some(client.workspace.target()),
+            class: Some(client.class.clone()),
+        }
+    }
+
+    /// The addresses of the clients under the selected group header.
+    pub fn selected_group(&self) -> Option<&[String]> {
+        match self.entries.get(self.state.selected()?)? {
+            Entry::Header(addresses) => Some(addresses),
+            Entry::Client(_) => None,
         }
     }
 }
 
 impl WidgetRef for ClientTable<'_> {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        StatefulWidget::render(self.table.clone(), area, buf, &mut self.state.clone());
+        scrolling::render_table(
+            &self.table,
+            &self.state,
+            self.len,
+            "No windows",
+            &self.viewport,
+            area,
+            buf,
+        );
     }
 }
+
+/// Moves the members of each group right after the group's first window, and
+/// returns the tree prefix to draw in front of every client.
+fn nest_groups(clients: Vec<Client>) -> (Vec<Client>, Vec<&'static str>) {
+    let is_listed = |address: &String| clients.iter().any(|client| client.address == *address);
+    let mut order = Vec::new();
+    for (index, client) in clients.iter().enumerate() {
+        match client.grouped.first() {
+            // Members get listed by their leader, unless it is filtered out
+            Some(leader) if *leader != client.address && is_listed(leader) => {}
+            Some(leader) if *leader == client.address => {
+                order.push((index, ""));
+                let members: Vec<usize> = client.grouped[1..]
+                    .iter()
+                    .filter_map(|member| clients.iter().position(|c| c.address == *member))
+                    .collect();
+                for (i, member) in members.iter().enumerate() {
+                    let prefix = if i + 1 == members.len() {
+                        "└ "
+                    } else {
+                        "├ "
+                    };
+                    order.push((*member, prefix));
+                }
+            }
+            _ => order.push((index, "")),
+        }
+    }
+
+    let prefixes = order.iter().map(|(_, prefix)| *prefix).collect();
+    let mut clients: Vec<Option<Client>> = clients.into_iter().map(Some).collect();
+    let clients = order
+        .iter()
+        .filter_map(|(index, _)| clients[*index].take())
+        .collect();
+
+    (clients, prefixes)
+}
@@ -0,0 +1,333 @@
+use std::cmp::Ordering;
+
+use ratatui::{layout::Constraint, style::Stylize, widgets::Row};
+use serde::{Deserialize, Serialize};
+
+use crate::hypr::{Client, Workspace};
+
+/// A column that can be shown in a table of `Item`s and sorted on.
+pub trait Column: Copy + PartialEq {
+    type Item;
+
+    fn title(self) -> &'static str;
+    fn width(self) -> Constraint;
+    fn cell(self, item: &Self::Item) -> String;
+    fn compare(self, a: &Self::Item, b: &Self::Item) -> Ordering;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientColumn {
+    Class,
+    Title,
+    Workspace,
+    Address,
+    Pid,
+    Floating,
+    Position,
+    Size,
+    InitialClass,
+    InitialTitle,
+    /// How many windows are in the client's group.
+    Group,
+    /// How recently the client was focused, 0 being the focused one.
+    FocusHistory,
+}
+
+impl ClientColumn {
+    pub const DEFAULT: [ClientColumn; 3] = [
+        ClientColumn::Class,
+        ClientColumn::Title,
+        ClientColumn::Workspace,
+    ];
+}
+
+impl Column for ClientColumn {
+    type Item = Client;
+
+    fn title(self) -> &'static str {
+        match self {
+            ClientColumn::Class => "Class",
+            ClientColumn::Title => "Title",
+            ClientColumn::Workspace => "Workspace",
+            ClientColumn::Address => "Address",
+            ClientColumn::Pid => "PID",
+            ClientColumn::Floating => "Floating",
+            ClientColumn::Position => "Position",
+            ClientColumn::Size => "Size",
+            ClientColumn::InitialClass => "Initial Class",
+            ClientColumn::InitialTitle => "Initial Title",
+            ClientColumn::Group => "Group",
+            ClientColumn::FocusHistory => "Recent",
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            ClientColumn::Class | ClientColumn::InitialClass => Constraint::Max(15),
+            ClientColumn::Title => Constraint::Min(30),
+            ClientColumn::InitialTitle => Constraint::Min(20),
+            ClientColumn::Workspace | ClientColumn::Floating => Constraint::Max(10),
+            ClientColumn::Address => Constraint::Max(14),
+            ClientColumn::Position | ClientColumn::Size => Constraint::Max(11),
+            ClientColumn::Pid => Constraint::Max(8),
+            ClientColumn::Group | ClientColumn::FocusHistory => Constraint::Max(6),
+        }
+    }
+
+    fn cell(self, client: &Client) -> String {
+        match self {
+            ClientColumn::Class => client.class.clone(),
+            ClientColumn::Title => client.title.clone(),
+            ClientColumn::Workspace => client.workspace.name.clone(),
+            ClientColumn::Address => client.address.clone(),
+            ClientColumn::Pid => client.pid.to_string(),
+            ClientColumn::Floating if client.floating => "yes".to_string(),
+            ClientColumn::Floating => String::new(),
+            ClientColumn::Position => format!("{},{}", client.at[0], client.at[1]),
+            ClientColumn::Size => format!("{}x{}", client.size[0], client.size[1]),
+            ClientColumn::InitialClass => client.initial_class.clone(),
+            ClientColumn::InitialTitle => client.initial_title.clone(),
+            ClientColumn::Group => match client.grouped.len() {
+                0 => String::new(),
+                len => len.to_string(),
+            },
+            ClientColumn::FocusHistory => client.focus_id.to_string(),
+        }
+    }
+
+    fn compare(self, a: &Client, b: &Client) -> Ordering {
+        match self {
+            ClientColumn::Class => a.class.to_lowercase().cmp(&b.class.to_lowercase()),
+            ClientColumn::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            ClientColumn::Workspace => a.workspace.id.cmp(&b.workspace.id),
+            ClientColumn::Address => a.address.cmp(&b.address),
+            ClientColumn::Pid => a.pid.cmp(&b.pid),
+            ClientColumn::Floating => a.floating.cmp(&b.floating),
+            ClientColumn::Position => a.at.cmp(&b.at),
+            ClientColumn::Size => (a.size[0] * a.size[1]).cmp(&(b.size[0] * b.size[1])),
+            ClientColumn::InitialClass => a
+                .initial_class
+                .to_lowercase()
+                .cmp(&b.initial_class.to_lowercase()),
+            ClientColumn::InitialTitle => a
+                .initial_title
+                .to_lowercase()
+                .cmp(&b.initial_title.to_lowercase()),
+            ClientColumn::Group => a.grouped.len().cmp(&b.grouped.len()),
+            ClientColumn::FocusHistory => a.focus_id.cmp(&b.focus_id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceColumn {
+    Id,
+    Name,
+    Monitor,
+    /// How many windows are on the workspace.
+    Clients,
+}
+
+impl WorkspaceColumn {
+    pub const DEFAULT: [WorkspaceColumn; 4] = [
+        WorkspaceColumn::Id,
+        WorkspaceColumn::Name,
+        WorkspaceColumn::Monitor,
+        WorkspaceColumn::Clients,
+    ];
+}
+
+impl Column for WorkspaceColumn {
+    type Item = Workspace;
+
+    fn title(self) -> &'static str {
+        match self {
+            WorkspaceColumn::Id => "ID",
+            WorkspaceColumn::Name => "Name",
+            WorkspaceColumn::Monitor => "Monitor",
+            WorkspaceColumn::Clients => "Clients",
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            WorkspaceColumn::Name => Constraint::Min(30),
+            _ => Constraint::Max(10),
+        }
+    }
+
+    fn cell(self, workspace: &Workspace) -> String {
+        match self {
+            WorkspaceColumn::Id => workspace.id.to_string(),
+            WorkspaceColumn::Name => workspace.name.clone(),
+            WorkspaceColumn::Monitor => workspace.monitor_id.to_string(),
+            WorkspaceColumn::Clients => workspace.windows.to_string(),
+        }
+    }
+
+    fn compare(self, a: &Workspace, b: &Workspace) -> Ordering {
+        match self {
+            WorkspaceColumn::Id => a.id.cmp(&b.id),
+            WorkspaceColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            WorkspaceColumn::Monitor => a.monitor_id.cmp(&b.monitor_id),
+            WorkspaceColumn::Clients => a.windows.cmp(&b.windows),
+        }
+    }
+}
+
+/// The column a table is sorted on, kept in the state file between runs.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Sort<C> {
+    pub column: C,
+    pub descending: bool,
+}
+
+/// The columns a table shows, in order, and the one it is sorted on.
+#[derive(Debug, Clone)]
+pub struct Columns<C> {
+    pub shown: Vec<C>,
+    pub sort: Option<Sort<C>>,
+}
+
+impl<C: Column> Columns<C> {
+    pub fn new(shown: Vec<C>, sort: Option<Sort<C>>) -> Columns<C> {
+        Columns { shown, sort }
+    }
+
+    /// Steps through sorting on each shown column ascending then descending,
+    /// then back to the order Hyprland lists items in.
+    pub fn cycle_sort(&mut self) {
+        let position = |column| self.shown.iter().position(|shown| *shown == column);
+        self.sort = match self.sort {
+            None => self.shown.first().map(|column| Sort {
+                column: *column,
+                descending: false,
+            }),
+            Some(Sort {
+                column,
+                descending: false,
+            }) if position(column).is_some() => Some(Sort {
+                column,
+                descending: true,
+            }),
+            Some(Sort { column, .. }) => position(column)
+                .and_then(|index| self.shown.get(index + 1))
+                .map(|column| Sort {
+                    column: *column,
+                    descending: false,
+                }),
+        };
+    }
+
+    /// Sorts `items` on the sort column. The sort is stable, so ties keep
+    /// Hyprland's order.
+    pub fn sort(&self, items: &mut [C::Item]) {
+        if let Some(sort) = self.sort {
+            items.sort_by(|a, b| {
+                let ordering = sort.column.compare(a, b);
+                if sort.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+    }
+
+    /// Widths of the shown columns, after a narrow one for the active marker.
+    pub fn widths(&self) -> Vec<Constraint> {
+        let mut widths = vec![Constraint::Length(1)];
+        widths.extend(self.shown.iter().map(|column| column.width()));
+        widths
+    }
+
+    /// Column titles, with an arrow on the one the table is sorted on.
+    pub fn header<'a>(&self) -> Row<'a> {
+        let mut titles = vec![String::new()];
+        titles.extend(self.shown.iter().map(|column| match self.sort {
+            Some(sort) if sort.column == *column => {
+                let arrow = if sort.descending { "▼" } else { "▲" };
+                format!("{} {}", column.title(), arrow)
+            }
+            _ => column.title().to_string(),
+        }));
+        Row::new(titles).bold()
+    }
+
+    pub fn cells(&self, item: &C::Item) -> Vec<String> {
+        self.shown.iter().map(|column| column.cell(item)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sort(column: WorkspaceColumn, descending: bool) -> Option<Sort<WorkspaceColumn>> {
+        Some(Sort { column, descending })
+    }
+
+    fn workspace(id: i32, name: &str) -> Workspace {
+        Workspace {
+            id,
+            name: name.to_string(),
+            monitor_id: 0,
+            windows: 0,
+        }
+    }
+
+    #[test]
+    fn cycling_goes_through_each_column_both_ways() {
+        let mut columns = Columns::new(vec![WorkspaceColumn::Id, WorkspaceColumn::Name], None);
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            columns.cycle_sort();
+            seen.push(columns.sort);
+        }
+
+        assert_eq!(
+            seen,
+            [
+                sort(WorkspaceColumn::Id, false),
+                sort(WorkspaceColumn::Id, true),
+                sort(WorkspaceColumn::Name, false),
+                sort(WorkspaceColumn::Name, true),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn a_sort_on_a_hidden_column_is_dropped() {
+        let mut columns = Columns::new(
+            vec![WorkspaceColumn::Id],
+            sort(WorkspaceColumn::Clients, false),
+        );
+        columns.cycle_sort();
+        assert_eq!(columns.sort, None);
+    }
+
+    #[test]
+    fn cycling_without_columns_never_sorts() {
+        let mut columns = Columns::<WorkspaceColumn>::new(Vec::new(), None);
+        columns.cycle_sort();
+        assert_eq!(columns.sort, None);
+    }
+
+    #[test]
+    fn names_sort_ignoring_case() {
+        let mut workspaces = vec![workspace(1, "b"), workspace(2, "C"), workspace(3, "A")];
+        let mut columns = Columns::new(vec![WorkspaceColumn::Name], None);
+        columns.cycle_sort();
+        columns.sort(&mut workspaces);
+        let ids: Vec<i32> = workspaces.iter().map(|workspace| workspace.id).collect();
+        assert_eq!(ids, [3, 1, 2]);
+
+        columns.cycle_sort();
+        columns.sort(&mut workspaces);
+        let ids: Vec<i32> = workspaces.iter().map(|workspace| workspace.id).collect();
+        assert_eq!(ids, [2, 1, 3]);
+    }
+}
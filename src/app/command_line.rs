@@ -0,0 +1,281 @@
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Widget, WidgetRef},
+};
+
+use crate::state::State;
+
+use super::{
+    input_history::{self, InputHistory},
+    text_input::TextInput,
+};
+
+/// Commands handled by wmt itself rather than sent to Hyprland.
+pub const ACTIONS: &[&str] = &[
+    "back", "console", "help", "history", "quit", "refresh", "undo",
+];
+
+/// Hyprland's dispatchers, for completion. Anything else is still sent, so
+/// dispatchers from newer versions or plugins work too.
+const DISPATCHERS: &[&str] = &[
+    "alterzorder",
+    "bringactivetotop",
+    "centerwindow",
+    "changegroupactive",
+    "closewindow",
+    "cyclenext",
+    "denywindowfromgroup",
+    "dpms",
+    "event",
+    "exec",
+    "execr",
+    "exit",
+    "focuscurrentorlast",
+    "focusmonitor",
+    "focusurgentorlast",
+    "focuswindow",
+    "focusworkspaceoncurrentmonitor",
+    "forcekillactive",
+    "forcerendererreload",
+    "fullscreen",
+    "fullscreenstate",
+    "global",
+    "killactive",
+    "killwindow",
+    "layoutmsg",
+    "lockactivegroup",
+    "lockgroups",
+    "movecurrentworkspacetomonitor",
+    "movecursor",
+    "movecursortocorner",
+    "movefocus",
+    "movegroupwindow",
+    "moveintogroup",
+    "moveoutofgroup",
+    "movetoworkspace",
+    "movetoworkspacesilent",
+    "movewindow",
+    "movewindoworgroup",
+    "movewindowpixel",
+    "moveworkspacetomonitor",
+    "pass",
+    "pin",
+    "pseudo",
+    "renameworkspace",
+    "resizeactive",
+    "resizewindowpixel",
+    "sendshortcut",
+    "setfloating",
+    "setignoregrouplock",
+    "setprop",
+    "settiled",
+    "signal",
+    "signalwindow",
+    "submap",
+    "swapactiveworkspaces",
+    "swapnext",
+    "swapsplit",
+    "swapwindow",
+    "tagwindow",
+    "togglefloating",
+    "togglegroup",
+    "togglespecialworkspace",
+    "togglesplit",
+    "toggleswallow",
+    "workspace",
+];
+
+const PLACEHOLDERS: &[&str] = &["{address}", "{workspace}", "{class}"];
+
+/// What the cursor is on, to fill in the placeholders of a command.
+#[derive(Default)]
+pub struct Selection {
+    pub address: Option<String>,
+    /// How dispatchers refer to the workspace, see [`crate::hypr::ClientWorkspace::target`].
+    pub workspace: Option<String>,
+    pub class: Option<String>,
+}
+
+impl Selection {
+    /// Replaces each placeholder in `command`, failing if one of them has
+    /// nothing selected to stand for.
+    pub fn fill(&self, command: &str) -> Result<String> {
+        let mut command = command.to_string();
+        for (placeholder, value) in [
+            ("{address}", &self.address),
+            ("{workspace}", &self.workspace),
+            ("{class}", &self.class),
+        ] {
+            if !command.contains(placeholder) {
+                continue;
+            }
+            let Some(value) = value else {
+                bail!("Nothing selected to fill in {}", placeholder);
+            };
+            command = command.replace(placeholder, value);
+        }
+
+        Ok(command)
+    }
+}
+
+/// What the command line wants done after a key.
+pub enum CommandLineEvent {
+    Run(String),
+    Cancel,
+}
+
+/// The `:` prompt, with completion and the history of past commands.
+pub struct CommandLine {
+    input: TextInput,
+    history: InputHistory,
+    /// What was typed before the first Tab, and how many times it was pressed.
+    completion: Option<(String, usize)>,
+}
+
+impl CommandLine {
+    pub fn new() -> Result<CommandLine> {
+        let state = State::load().context("Loading the command history")?;
+        Ok(CommandLine {
+            input: TextInput::default(),
+            history: InputHistory::new(state.command_history),
+            completion: None,
+        })
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<CommandLineEvent> {
+        if key_event.code != KeyCode::Tab {
+            self.completion = None;
+        }
+        match key_event.code {
+            KeyCode::Esc => Some(CommandLineEvent::Cancel),
+            KeyCode::Backspace if self.input.value().is_empty() => Some(CommandLineEvent::Cancel),
+            KeyCode::Enter => match self.input.value().trim() {
+                "" => Some(CommandLineEvent::Cancel),
+                command => Some(CommandLineEvent::Run(command.to_string())),
+            },
+            KeyCode::Tab => {
+                self.complete();
+                None
+            }
+            KeyCode::Up => {
+                let command = self.history.older()?.to_string();
+                self.input.set_value(command);
+                None
+            }
+            KeyCode::Down => {
+                let command = self.history.newer()?.to_string();
+                self.input.set_value(command);
+                None
+            }
+            _ => {
+                self.input.handle_key_event(key_event);
+                None
+            }
+        }
+    }
+
+    /// Completes the last word, a command name when it is the first word and
+    /// a placeholder when it has an unclosed `{`. Pressing Tab again cycles
+    /// through the other matches.
+    fn complete(&mut self) {
+        let (typed, count) = match self.completion.take() {
+            Some((typed, count)) => (typed, count + 1),
+            None => (self.input.value().to_string(), 0),
+        };
+        let word_start = typed.rfind(' ').map_or(0, |index| index + 1);
+        let (start, candidates) = match typed[word_start..].rfind('{') {
+            Some(brace) if !typed[word_start + brace..].contains('}') => {
+                (word_start + brace, PLACEHOLDERS)
+            }
+            _ if word_start == 0 => (0, ACTIONS),
+            _ => return,
+        };
+        let prefix = &typed[start..];
+        let matches: Vec<&str> = candidates
+            .iter()
+            .chain(if start == 0 { DISPATCHERS } else { &[] })
+            .filter(|candidate| candidate.starts_with(prefix))
+            .copied()
+            .collect();
+        if matches.is_empty() {
+            return;
+        }
+
+        let completed = matches[count % matches.len()];
+        let suffix = if matches.len() == 1 && start == 0 {
+            " "
+        } else {
+            ""
+        };
+        self.input
+            .set_value(format!("{}{}{}", &typed[..start], completed, suffix));
+        self.completion = Some((typed, count));
+    }
+}
+
+/// Adds `command` to the history kept in the state file.
+pub fn record(command: &str) -> Result<()> {
+    let mut state = State::load()?;
+    input_history::add(&mut state.command_history, command);
+    state.save()
+}
+
+impl WidgetRef for CommandLine {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Line::from(vec![
+            ":".blue().bold(),
+            self.input.value().to_string().into(),
+            "█".into(),
+        ])
+        .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection() -> Selection {
+        Selection {
+            address: Some("0x1234".to_string()),
+            workspace: Some("name:web".to_string()),
+            class: None,
+        }
+    }
+
+    #[test]
+    fn placeholders_are_filled_in() {
+        assert_eq!(
+            selection()
+                .fill("movetoworkspacesilent {workspace},address:{address}")
+                .unwrap(),
+            "movetoworkspacesilent name:web,address:0x1234"
+        );
+        assert_eq!(
+            selection()
+                .fill("focuswindow address:{address}; pin address:{address}")
+                .unwrap(),
+            "focuswindow address:0x1234; pin address:0x1234"
+        );
+    }
+
+    #[test]
+    fn commands_without_placeholders_are_left_alone() {
+        assert_eq!(
+            Selection::default().fill("workspace 2").unwrap(),
+            "workspace 2"
+        );
+    }
+
+    #[test]
+    fn a_placeholder_with_nothing_selected_fails() {
+        let err = selection().fill("focuswindow class:{class}").unwrap_err();
+        assert_eq!(err.to_string(), "Nothing selected to fill in {class}");
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result};
+
+use crate::{
+    hypr::{self, ClientWorkspace},
+    minimize::{self, MINIMIZED_WORKSPACE},
+};
+
+/// Something wmt changed that can be reverted.
+pub enum Change {
+    /// A client was moved away from the `from` workspace.
+    Moved { address: String, from: String },
+    /// A client was minimized from the `from` workspace.
+    Minimized { address: String, from: String },
+    /// A minimized client was restored, and came from `origin` before that.
+    Restored {
+        address: String,
+        origin: ClientWorkspace,
+    },
+}
+
+/// A single user action, which may have changed several things at once.
+pub struct Operation {
+    pub description: String,
+    pub changes: Vec<Change>,
+}
+
+impl Operation {
+    pub fn new(description: String, changes: Vec<Change>) -> Operation {
+        Operation {
+            description,
+            changes,
+        }
+    }
+
+    /// Runs the inverse of every change in one batch. Restored clients are
+    /// minimized again, remembering where they came from, and minimized ones
+    /// are forgotten.
+    fn undo(&self) -> Result<()> {
+        let moves: Vec<(String, String)> = self
+            .changes
+            .iter()
+            .map(|change| match change {
+                Change::Moved { address, from } | Change::Minimized { address, from } => {
+                    (from.clone(), address.clone())
+                }
+                Change::Restored { address, .. } => {
+                    (MINIMIZED_WORKSPACE.to_string(), address.clone())
+                }
+            })
+            .collect();
+        hypr::send_many_to_workspaces(&moves)?;
+
+        for change in &self.changes {
+            match change {
+                Change::Restored { address, origin } => minimize::remember(address, origin)?,
+                Change::Minimized { address, .. } => minimize::forget(address)?,
+                Change::Moved { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Operations done during this run of wmt, oldest first.
+#[derive(Default)]
+pub struct History {
+    operations: Vec<Operation>,
+}
+
+impl History {
+    pub fn record(&mut self, operation: Operation) {
+        self.operations.push(operation);
+    }
+
+    /// Descriptions of the operations, most recent first.
+    pub fn descriptions(&self) -> Vec<String> {
+        self.operations
+            .iter()
+            .rev()
+            .map(|operation| operation.description.clone())
+            .collect()
+    }
+
+    /// Reverts the `count` most recent operations, newest first. Stops at the
+    /// first one that fails, which stays in the history.
+    pub fn undo(&mut self, count: usize) -> Result<()> {
+        for _ in 0..count {
+            let Some(operation) = self.operations.last() else {
+                break;
+            };
+            operation
+                .undo()
+                .with_context(|| format!("Undoing {}", operation.description))?;
+            self.operations.pop();
+        }
+
+        Ok(())
+    }
+}
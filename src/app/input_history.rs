@@ -0,0 +1,112 @@
+/// How many entries a history keeps.
+const HISTORY_LEN: usize = 100;
+
+/// Past inputs of a prompt, browsed with Up and Down like in a shell.
+pub struct InputHistory {
+    /// Oldest first.
+    entries: Vec<String>,
+    /// Position while browsing, none while back on what is being typed.
+    index: Option<usize>,
+}
+
+impl InputHistory {
+    pub fn new(entries: Vec<String>) -> InputHistory {
+        InputHistory {
+            entries,
+            index: None,
+        }
+    }
+
+    /// Steps back to the entry before the one shown, or to the newest one.
+    /// Returns none if there is no history.
+    pub fn older(&mut self) -> Option<&str> {
+        let index = match self.index {
+            Some(index) => index.saturating_sub(1),
+            None => self.entries.len().checked_sub(1)?,
+        };
+        self.index = Some(index);
+
+        Some(&self.entries[index])
+    }
+
+    /// Steps forward to the entry after the one shown, or to an empty input
+    /// past the newest. Returns none if not browsing.
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.index?;
+        self.index = (index + 1 < self.entries.len()).then_some(index + 1);
+
+        Some(self.index.map_or("", |index| &self.entries[index]))
+    }
+
+    /// Adds `entry` as the newest and stops browsing.
+    pub fn push(&mut self, entry: &str) {
+        add(&mut self.entries, entry);
+        self.index = None;
+    }
+}
+
+/// Adds `entry` to `entries` as the newest, dropping an earlier copy of it
+/// and the oldest entries past the limit.
+pub fn add(entries: &mut Vec<String>, entry: &str) {
+    entries.retain(|past| past != entry);
+    entries.push(entry.to_string());
+    let excess = entries.len().saturating_sub(HISTORY_LEN);
+    entries.drain(..excess);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(entries: &[&str]) -> InputHistory {
+        InputHistory::new(entries.iter().map(|entry| entry.to_string()).collect())
+    }
+
+    #[test]
+    fn older_stops_at_the_oldest_entry() {
+        let mut history = history(&["a", "b", "c"]);
+        assert_eq!(history.older(), Some("c"));
+        assert_eq!(history.older(), Some("b"));
+        assert_eq!(history.older(), Some("a"));
+        assert_eq!(history.older(), Some("a"));
+    }
+
+    #[test]
+    fn newer_goes_back_to_an_empty_input() {
+        let mut history = history(&["a", "b"]);
+        assert_eq!(history.newer(), None);
+        history.older();
+        history.older();
+        assert_eq!(history.newer(), Some("b"));
+        assert_eq!(history.newer(), Some(""));
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older(), Some("b"));
+    }
+
+    #[test]
+    fn an_empty_history_has_nothing_to_show() {
+        let mut history = history(&[]);
+        assert_eq!(history.older(), None);
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn pushing_stops_browsing_and_moves_repeats_to_the_end() {
+        let mut history = history(&["a", "b"]);
+        history.older();
+        history.push("a");
+        assert_eq!(history.older(), Some("a"));
+        assert_eq!(history.older(), Some("b"));
+        assert_eq!(history.older(), Some("b"));
+    }
+
+    #[test]
+    fn the_oldest_entries_are_dropped_past_the_limit() {
+        let mut entries = Vec::new();
+        for i in 0..HISTORY_LEN + 5 {
+            add(&mut entries, &i.to_string());
+        }
+        assert_eq!(entries.len(), HISTORY_LEN);
+        assert_eq!(entries.first().map(String::as_str), Some("5"));
+    }
+}
@@ -0,0 +1,175 @@
+use std::{iter::Peekable, mem, str::Chars};
+
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+};
+
+/// How much each level of nesting is indented.
+const INDENT: &str = "  ";
+
+enum Token {
+    Punctuation(char),
+    /// A string with its quotes and escapes, as it appears in the source.
+    String(String),
+    /// A number, `true`, `false` or `null`.
+    Scalar(String),
+}
+
+/// Lays out `json` with one value per line and colors it. Keys keep the
+/// order they have in `json`, unlike going through `serde_json::Value`.
+/// `json` is expected to be valid, anything else comes out mangled.
+pub fn highlight(json: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Span<'static>> = Vec::new();
+    let mut depth = 0;
+    let mut tokens = tokenize(json).into_iter().peekable();
+    let mut new_line = |line: &mut Vec<Span<'static>>, depth: usize| {
+        lines.push(Line::from(mem::take(line)));
+        line.push(INDENT.repeat(depth).into());
+    };
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Punctuation(open @ ('{' | '[')) => {
+                line.push(open.to_string().into());
+                let close = if open == '{' { '}' } else { ']' };
+                if matches!(tokens.peek(), Some(Token::Punctuation(c)) if *c == close) {
+                    tokens.next();
+                    line.push(close.to_string().into());
+                } else {
+                    depth += 1;
+                    new_line(&mut line, depth);
+                }
+            }
+            Token::Punctuation(close @ ('}' | ']')) => {
+                depth = depth.saturating_sub(1);
+                new_line(&mut line, depth);
+                line.push(close.to_string().into());
+            }
+            Token::Punctuation(',') => {
+                line.push(",".into());
+                new_line(&mut line, depth);
+            }
+            Token::Punctuation(':') => line.push(": ".into()),
+            Token::Punctuation(other) => line.push(other.to_string().into()),
+            Token::String(string) => {
+                if matches!(tokens.peek(), Some(Token::Punctuation(':'))) {
+                    line.push(string.blue());
+                } else {
+                    line.push(string.green());
+                }
+            }
+            Token::Scalar(scalar) => match scalar.as_str() {
+                "true" | "false" | "null" => line.push(scalar.magenta()),
+                _ => line.push(scalar.yellow()),
+            },
+        }
+    }
+    if !line.is_empty() {
+        lines.push(Line::from(line));
+    }
+
+    lines
+}
+
+fn tokenize(json: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = json.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '{' | '}' | '[' | ']' | ',' | ':' => tokens.push(Token::Punctuation(c)),
+            '"' => tokens.push(Token::String(read_string(&mut chars))),
+            c => {
+                let mut scalar = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "{}[],:\"".contains(next) {
+                        break;
+                    }
+                    scalar.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Scalar(scalar));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Reads the rest of a string whose opening quote was just read.
+fn read_string(chars: &mut Peekable<Chars>) -> String {
+    let mut string = String::from('"');
+    while let Some(c) = chars.next() {
+        string.push(c);
+        match c {
+            '\\' => string.extend(chars.next()),
+            '"' => break,
+            _ => {}
+        }
+    }
+
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    /// The color of the span reading `content`.
+    fn color(lines: &[Line], content: &str) -> Option<Color> {
+        lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .find(|span| span.content == content)?
+            .style
+            .fg
+    }
+
+    #[test]
+    fn values_go_on_their_own_lines() {
+        let lines = highlight(r#"{"b":1,"a":[true,null],"c":{}}"#);
+        assert_eq!(
+            text(&lines),
+            [
+                "{",
+                r#"  "b": 1,"#,
+                r#"  "a": ["#,
+                "    true,",
+                "    null",
+                "  ],",
+                r#"  "c": {}"#,
+                "}",
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_and_values_are_colored_apart() {
+        let lines = highlight(r#"{"name":"web","id":-1337,"pinned":false}"#);
+        assert_eq!(color(&lines, r#""name""#), Some(Color::Blue));
+        assert_eq!(color(&lines, r#""web""#), Some(Color::Green));
+        assert_eq!(color(&lines, "-1337"), Some(Color::Yellow));
+        assert_eq!(color(&lines, "false"), Some(Color::Magenta));
+    }
+
+    #[test]
+    fn strings_keep_their_escapes() {
+        let lines = highlight(r#"["say \"hi\", {ok}"]"#);
+        assert_eq!(text(&lines), ["[", r#"  "say \"hi\", {ok}""#, "]"]);
+    }
+
+    #[test]
+    fn scalars_stand_alone() {
+        assert_eq!(text(&highlight("42")), ["42"]);
+        assert_eq!(text(&highlight("[]")), ["[]"]);
+        assert!(highlight("").is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Stylize,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::hypr::{Client, Monitor};
+
+/// Draws the windows of a workspace as boxes scaled down from their real
+/// position and size on the monitor.
+pub struct Minimap<'a> {
+    monitor: &'a Monitor,
+    clients: Vec<&'a Client>,
+}
+
+impl<'a> Minimap<'a> {
+    pub fn new(monitor: &'a Monitor, mut clients: Vec<&'a Client>) -> Minimap<'a> {
+        // Floating windows sit on top of the tiled ones
+        clients.sort_by_key(|client| client.floating);
+        Minimap { monitor, clients }
+    }
+
+    /// Maps a window's layout coordinates onto `area`, keeping at least
+    /// enough room for a border.
+    fn scale(&self, client: &Client, area: Rect) -> Rect {
+        let (width, height) = self.monitor.logical_size();
+        let scale_x = |x: i32| {
+            let x = (x - self.monitor.x).clamp(0, width) as f32;
+            (x * area.width as f32 / width.max(1) as f32).round() as u16
+        };
+        let scale_y = |y: i32| {
+            let y = (y - self.monitor.y).clamp(0, height) as f32;
+            (y * area.height as f32 / height.max(1) as f32).round() as u16
+        };
+
+        let left = scale_x(client.at[0]).min(area.width.saturating_sub(2));
+        let top = scale_y(client.at[1]).min(area.height.saturating_sub(2));
+        let right = scale_x(client.at[0] + client.size[0]).max(left + 2);
+        let bottom = scale_y(client.at[1] + client.size[1]).max(top + 2);
+
+        Rect::new(area.x + left, area.y + top, right - left, bottom - top).intersection(area)
+    }
+}
+
+impl Widget for Minimap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.clients.is_empty() {
+            Paragraph::new("No windows".italic())
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        }
+
+        for client in &self.clients {
+            let window = self.scale(client, area);
+            Clear.render(window, buf);
+            Block::bordered()
+                .title(client.class.clone())
+                .render(window, buf);
+        }
+    }
+}
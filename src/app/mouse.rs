@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Position;
+
+/// How many rows a turn of the mouse wheel moves the cursor.
+pub const SCROLL_ROWS: usize = 3;
+
+/// Two clicks on the same spot within this long make a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// Tells double clicks apart, as terminals only report single ones.
+#[derive(Default)]
+pub struct Clicks {
+    last: Option<(Instant, Position)>,
+}
+
+impl Clicks {
+    /// Records a click at `column`, `row`, returning whether it completes a
+    /// double click.
+    pub fn is_double(&mut self, column: u16, row: u16) -> bool {
+        let now = Instant::now();
+        let position = Position::new(column, row);
+        let double = self.last.is_some_and(|(at, last)| {
+            last == position && now.duration_since(at) <= DOUBLE_CLICK_TIME
+        });
+        // A third click starts over instead of making another double click
+        self.last = if double { None } else { Some((now, position)) };
+
+        double
+    }
+}
@@ -0,0 +1,336 @@
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::Stylize,
+    widgets::{
+        Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Table,
+        TableState, Widget,
+    },
+};
+
+/// A way to move the cursor of a table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Motion {
+    Down(usize),
+    Up(usize),
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+}
+
+impl Motion {
+    /// Where the cursor ends up from `selected` in a table of `len` rows
+    /// that shows `page` of them at a time.
+    pub fn apply(self, selected: usize, len: usize, page: usize) -> usize {
+        let last = len.saturating_sub(1);
+        let half_page = (page / 2).max(1);
+        match self {
+            Motion::Down(count) => selected.saturating_add(count).min(last),
+            Motion::Up(count) => selected.saturating_sub(count),
+            Motion::HalfPageDown => (selected + half_page).min(last),
+            Motion::HalfPageUp => selected.saturating_sub(half_page),
+            Motion::PageDown => (selected + page).min(last),
+            Motion::PageUp => selected.saturating_sub(page),
+            Motion::Top => 0,
+            Motion::Bottom => last,
+        }
+    }
+}
+
+/// Turns keys into motions, with vim-style counts such as `5j`. As in vim,
+/// `gg` goes to the top, which leaves `g<digit>` free for picking workspaces.
+#[derive(Default)]
+pub struct MotionKeys {
+    count: Option<usize>,
+    /// Whether the last key was a `g` waiting for another.
+    g_pending: bool,
+}
+
+impl MotionKeys {
+    /// The digit of a `g<digit>` key, for screens that pick workspaces by
+    /// number. Drops any pending count.
+    pub fn workspace_digit(&mut self, key_event: KeyEvent) -> Option<char> {
+        let KeyCode::Char(digit @ '0'..='9') = key_event.code else {
+            return None;
+        };
+        if !self.g_pending {
+            return None;
+        }
+        self.g_pending = false;
+        self.count = None;
+
+        Some(digit)
+    }
+
+    /// Adds a digit to the count for the next motion. Returns false for
+    /// keys that aren't part of a count.
+    pub fn handle_count(&mut self, key_event: KeyEvent) -> bool {
+        let KeyCode::Char(digit) = key_event.code else {
+            return false;
+        };
+        let Some(digit) = digit.to_digit(10) else {
+            return false;
+        };
+        // A leading 0 isn't a count
+        if digit == 0 && self.count.is_none() {
+            return false;
+        }
+        let count = self.count.unwrap_or(0).saturating_mul(10);
+        self.count = Some(count.saturating_add(digit as usize));
+        self.g_pending = false;
+
+        true
+    }
+
+    /// The motion `key_event` stands for, if any. Any other key drops the
+    /// pending count.
+    pub fn motion(&mut self, key_event: KeyEvent) -> Option<Motion> {
+        let count = self.count.take().unwrap_or(1);
+        let g_pending = std::mem::take(&mut self.g_pending);
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Char('d') if ctrl => Some(Motion::HalfPageDown),
+            KeyCode::Char('u') if ctrl => Some(Motion::HalfPageUp),
+            _ if ctrl => None,
+            KeyCode::Down | KeyCode::Char('j') => Some(Motion::Down(count)),
+            KeyCode::Up | KeyCode::Char('k') => Some(Motion::Up(count)),
+            KeyCode::PageDown => Some(Motion::PageDown),
+            KeyCode::PageUp => Some(Motion::PageUp),
+            KeyCode::Char('g') if g_pending => Some(Motion::Top),
+            KeyCode::Char('g') => {
+                self.g_pending = true;
+                None
+            }
+            KeyCode::Home => Some(Motion::Top),
+            KeyCode::End | KeyCode::Char('G') => Some(Motion::Bottom),
+            _ => None,
+        }
+    }
+}
+
+/// Where a table was last drawn, for paging and for finding the row under
+/// the mouse.
+#[derive(Default)]
+pub struct Viewport {
+    /// The area the rows were drawn in, below the header.
+    body: Cell<Rect>,
+    /// The first row on screen.
+    offset: Cell<usize>,
+}
+
+impl Viewport {
+    /// How many rows fit on screen.
+    pub fn page(&self) -> usize {
+        (self.body.get().height as usize).max(1)
+    }
+
+    /// Index of the row drawn at `column`, `row` on the terminal.
+    pub fn row_at(&self, column: u16, row: u16, len: usize) -> Option<usize> {
+        let body = self.body.get();
+        if !body.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = self.offset.get() + (row - body.y) as usize;
+        (index < len).then_some(index)
+    }
+}
+
+/// Moves the selection of `state` by `motion`.
+pub fn move_cursor(state: &mut TableState, motion: Motion, len: usize, page: usize) {
+    if len == 0 {
+        return;
+    }
+    let selected = state.selected().unwrap_or(0);
+    state.select(Some(motion.apply(selected, len, page)));
+}
+
+/// Renders a table of `len` rows, with a scrollbar when they don't all fit
+/// and `placeholder` when there are none, and records where in `viewport`.
+/// Scrolling picks up from where the last render left off, so the cursor
+/// moves within the page instead of dragging it along.
+pub fn render_table(
+    table: &Table,
+    state: &TableState,
+    len: usize,
+    placeholder: &str,
+    viewport: &Viewport,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    // One line goes to the header
+    let [_, body] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
+    if len == 0 {
+        viewport.body.set(body);
+        StatefulWidget::render(table.clone(), area, buf, &mut TableState::default());
+        Paragraph::new(placeholder.italic())
+            .alignment(Alignment::Center)
+            .render(body, buf);
+        return;
+    }
+
+    let scrolls = len > body.height as usize;
+    let [table_area, scrollbar_area] = if scrolls {
+        Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area)
+    } else {
+        [area, Rect::default()]
+    };
+    viewport.body.set(body.intersection(table_area));
+
+    let mut state = state.clone().with_offset(viewport.offset.get());
+    StatefulWidget::render(table.clone(), table_area, buf, &mut state);
+    viewport.offset.set(state.offset());
+
+    if scrolls {
+        let [_, scrollbar_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(scrollbar_area);
+        let mut scrollbar_state = ScrollbarState::new(len)
+            .viewport_content_length(body.height as usize)
+            .position(state.selected().unwrap_or(0));
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .render(scrollbar_area, buf, &mut scrollbar_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn motions_stay_within_the_table() {
+        assert_eq!(Motion::Down(1).apply(3, 10, 4), 4);
+        assert_eq!(Motion::Down(20).apply(3, 10, 4), 9);
+        assert_eq!(Motion::Down(usize::MAX).apply(3, 10, 4), 9);
+        assert_eq!(Motion::Up(2).apply(3, 10, 4), 1);
+        assert_eq!(Motion::Up(20).apply(3, 10, 4), 0);
+        assert_eq!(Motion::Top.apply(3, 10, 4), 0);
+        assert_eq!(Motion::Bottom.apply(3, 10, 4), 9);
+    }
+
+    #[test]
+    fn pages_move_by_the_rows_on_screen() {
+        assert_eq!(Motion::PageDown.apply(0, 10, 4), 4);
+        assert_eq!(Motion::PageDown.apply(8, 10, 4), 9);
+        assert_eq!(Motion::PageUp.apply(6, 10, 4), 2);
+        assert_eq!(Motion::HalfPageDown.apply(0, 10, 4), 2);
+        assert_eq!(Motion::HalfPageUp.apply(1, 10, 4), 0);
+        // A one row page still moves half pages by a row
+        assert_eq!(Motion::HalfPageDown.apply(0, 10, 1), 1);
+    }
+
+    #[test]
+    fn empty_tables_have_nothing_to_select() {
+        for motion in [
+            Motion::Down(1),
+            Motion::Up(1),
+            Motion::PageDown,
+            Motion::Bottom,
+        ] {
+            assert_eq!(motion.apply(0, 0, 4), 0);
+
+            let mut state = TableState::default();
+            move_cursor(&mut state, motion, 0, 4);
+            assert_eq!(state.selected(), None);
+        }
+    }
+
+    #[test]
+    fn counts_repeat_the_next_motion() {
+        let mut motion_keys = MotionKeys::default();
+        assert!(motion_keys.handle_count(key(KeyCode::Char('1'))));
+        assert!(motion_keys.handle_count(key(KeyCode::Char('2'))));
+        assert_eq!(
+            motion_keys.motion(key(KeyCode::Char('j'))),
+            Some(Motion::Down(12))
+        );
+        assert_eq!(
+            motion_keys.motion(key(KeyCode::Char('k'))),
+            Some(Motion::Up(1))
+        );
+
+        // A leading 0 is left for the screen
+        assert!(!motion_keys.handle_count(key(KeyCode::Char('0'))));
+    }
+
+    #[test]
+    fn other_keys_drop_the_count() {
+        let mut motion_keys = MotionKeys::default();
+        motion_keys.handle_count(key(KeyCode::Char('5')));
+        assert_eq!(motion_keys.motion(key(KeyCode::Char('x'))), None);
+        assert_eq!(
+            motion_keys.motion(key(KeyCode::Down)),
+            Some(Motion::Down(1))
+        );
+    }
+
+    #[test]
+    fn g_prefixes_the_top_and_workspace_keys() {
+        let mut motion_keys = MotionKeys::default();
+        assert_eq!(motion_keys.motion(key(KeyCode::Char('g'))), None);
+        assert_eq!(
+            motion_keys.motion(key(KeyCode::Char('g'))),
+            Some(Motion::Top)
+        );
+
+        motion_keys.handle_count(key(KeyCode::Char('4')));
+        assert_eq!(motion_keys.motion(key(KeyCode::Char('g'))), None);
+        assert_eq!(
+            motion_keys.workspace_digit(key(KeyCode::Char('3'))),
+            Some('3')
+        );
+        // The count before the g was dropped along with it
+        assert_eq!(
+            motion_keys.motion(key(KeyCode::Char('j'))),
+            Some(Motion::Down(1))
+        );
+
+        assert_eq!(motion_keys.workspace_digit(key(KeyCode::Char('3'))), None);
+    }
+
+    fn viewport(body: Rect, offset: usize) -> Viewport {
+        let viewport = Viewport::default();
+        viewport.body.set(body);
+        viewport.offset.set(offset);
+        viewport
+    }
+
+    #[test]
+    fn rows_are_found_below_the_scroll_offset() {
+        let viewport = viewport(Rect::new(2, 1, 10, 3), 5);
+        assert_eq!(viewport.row_at(2, 1, 20), Some(5));
+        assert_eq!(viewport.row_at(11, 3, 20), Some(7));
+        assert_eq!(viewport.page(), 3);
+    }
+
+    #[test]
+    fn clicks_outside_the_rows_miss() {
+        let viewport = viewport(Rect::new(2, 1, 10, 3), 0);
+        // The header line and the border around the body
+        assert_eq!(viewport.row_at(2, 0, 20), None);
+        assert_eq!(viewport.row_at(1, 1, 20), None);
+        assert_eq!(viewport.row_at(12, 1, 20), None);
+        assert_eq!(viewport.row_at(2, 4, 20), None);
+        // Below the last row of a short table
+        assert_eq!(viewport.row_at(2, 3, 2), None);
+        assert_eq!(viewport.row_at(2, 1, 0), None);
+    }
+
+    #[test]
+    fn nothing_is_found_before_the_first_render() {
+        let viewport = Viewport::default();
+        assert_eq!(viewport.row_at(0, 0, 10), None);
+        assert_eq!(viewport.page(), 1);
+    }
+}
@@ -0,0 +1,49 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::Line,
+    widgets::{Widget, WidgetRef},
+};
+
+/// A single line of text typed by the user.
+#[derive(Default)]
+pub struct TextInput {
+    value: String,
+}
+
+impl TextInput {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+
+    /// Applies an editing key, returning false for keys it doesn't handle
+    /// such as Enter or Esc.
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char(c) => self.value.push(c),
+            KeyCode::Backspace => {
+                self.value.pop();
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl WidgetRef for TextInput {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Line::from(vec![
+            "> ".blue().bold(),
+            self.value.clone().into(),
+            "█".into(),
+        ])
+        .render(area, buf);
+    }
+}
@@ -1,79 +1,138 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::Rect,
     style::{Style, Stylize},
-    widgets::{Row, StatefulWidget, Table, TableState, WidgetRef},
+    widgets::{Row, Table, TableState, WidgetRef},
 };
 
 use crate::hypr::Workspace;
 
+use super::{
+    client_table::ACTIVE_MARKER,
+    columns::{Columns, WorkspaceColumn},
+    scrolling::{self, Motion, Viewport},
+};
+
 pub struct WorkspaceTable<'a> {
     state: TableState,
     table: Table<'a>,
     pub workspaces: Vec<Workspace>,
     len: usize,
+    viewport: Viewport,
+    /// Whether the first row is a "New workspace" entry.
+    new_workspace_row: bool,
 }
 
 impl<'a> WorkspaceTable<'_> {
-    pub fn new(workspaces: Vec<Workspace>) -> WorkspaceTable<'a> {
+    /// `active_id` is the workspace on screen, which gets marked in the table.
+    /// Workspaces are sorted as `columns` says.
+    pub fn new(
+        workspaces: Vec<Workspace>,
+        active_id: Option<i32>,
+        columns: &Columns<WorkspaceColumn>,
+    ) -> WorkspaceTable<'a> {
+        Self::build(workspaces, active_id, columns, false)
+    }
+
+    /// Like [`WorkspaceTable::new`], with a "New workspace" row at the top.
+    pub fn with_new_workspace_row(
+        workspaces: Vec<Workspace>,
+        active_id: Option<i32>,
+        columns: &Columns<WorkspaceColumn>,
+    ) -> WorkspaceTable<'a> {
+        Self::build(workspaces, active_id, columns, true)
+    }
+
+    fn build(
+        mut workspaces: Vec<Workspace>,
+        active_id: Option<i32>,
+        columns: &Columns<WorkspaceColumn>,
+        new_workspace_row: bool,
+    ) -> WorkspaceTable<'a> {
+        columns.sort(&mut workspaces);
         let state = TableState::default().with_selected(Some(0));
-        let widths = [
-            Constraint::Max(10),
-            Constraint::Min(30),
-            Constraint::Max(10),
-            Constraint::Max(10),
-        ];
-        let rows: Vec<Row> = workspaces
-            .iter()
-            .map(|workspace| {
-                Row::new(vec![
-                    workspace.id.to_string(),
-                    workspace.name.clone(),
-                    workspace.monitor_id.to_string().clone(),
-                    workspace.windows.to_string().clone(),
-                ])
-            })
-            .collect();
-        let table = Table::new(rows, widths)
-            .header(Row::new(vec!["ID", "Name", "Monitor", "Clients"]).bold())
+        let mut rows: Vec<Row> = Vec::new();
+        if new_workspace_row {
+            rows.push(Row::new(vec!["", "+", "New workspace"]).italic());
+        }
+        rows.extend(workspaces.iter().map(|workspace| {
+            let marker = if Some(workspace.id) == active_id {
+                ACTIVE_MARKER
+            } else {
+                ""
+            };
+            let mut cells = vec![marker.to_string()];
+            cells.extend(columns.cells(workspace));
+            Row::new(cells)
+        }));
+        let table = Table::new(rows, columns.widths())
+            .header(columns.header())
             .row_highlight_style(Style::new().reversed());
-        let len = workspaces.len();
+        let len = workspaces.len() + new_workspace_row as usize;
 
         WorkspaceTable {
             state,
             table,
             workspaces,
             len,
+            viewport: Viewport::default(),
+            new_workspace_row,
         }
     }
 
-    pub fn move_down(&mut self) {
-        if let Some(i) = self.state.selected() {
-            if i < self.len - 1 {
-                self.state.select_next();
-            }
-        }
+    pub fn move_cursor(&mut self, motion: Motion) {
+        scrolling::move_cursor(&mut self.state, motion, self.len, self.viewport.page());
     }
 
-    pub fn move_up(&mut self) {
-        if let Some(i) = self.state.selected() {
-            if i > 0 {
-                self.state.select_previous();
-            }
+    /// Selects the row at `column`, `row` on the terminal, returning false
+    /// if there is none there.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let Some(index) = self.viewport.row_at(column, row, self.len) else {
+            return false;
+        };
+        self.state.select(Some(index));
+
+        true
+    }
+
+    pub fn select_workspace(&mut self, id: i32) {
+        if let Some(index) = self
+            .workspaces
+            .iter()
+            .position(|workspace| workspace.id == id)
+        {
+            self.state
+                .select(Some(index + self.new_workspace_row as usize));
         }
     }
 
-    pub fn selected_workspace(&self) -> Option<u32> {
-        if let Some(index) = self.state.selected() {
-            return Some(self.workspaces.get(index)?.id);
-        } else {
-            return None;
+    pub fn selected(&self) -> Option<&Workspace> {
+        let mut index = self.state.selected()?;
+        if self.new_workspace_row {
+            index = index.checked_sub(1)?;
         }
+        self.workspaces.get(index)
+    }
+
+    pub fn selected_workspace(&self) -> Option<i32> {
+        Some(self.selected()?.id)
+    }
+
+    pub fn new_workspace_selected(&self) -> bool {
+        self.new_workspace_row && self.state.selected() == Some(0)
     }
 }
 
 impl WidgetRef for WorkspaceTable<'_> {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        StatefulWidget::render(self.table.clone(), area, buf, &mut self.state.clone());
+        scrolling::render_table(
+            &self.table,
+            &self.state,
+            self.len,
+            "No workspaces",
+            &self.viewport,
+            area,
+            buf,
+        );
     }
 }
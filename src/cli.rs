@@ -0,0 +1,58 @@
+use anyhow::{bail, Result};
+
+use crate::app::StartScreen;
+
+pub const USAGE: &str = "Usage: wmt [--mru]
+       wmt session save <name>
+       wmt session restore <name>
+       wmt organize [--dry-run]
+       wmt jump <letter>
+       wmt minimize
+       wmt restore
+
+Commands:
+  session save <name>     Save which workspace every window is on
+  session restore <name>  Move windows back to where a saved session had them
+  organize [--dry-run]    Move windows to the workspaces the config rules want
+  jump <letter>           Focus the window marked with <letter>
+  minimize                Hide the focused window in special:minimized
+  restore                 Bring back the most recently minimized window
+
+Options:
+  --mru       Start on the recent windows (alt-tab) screen
+  -h, --help  Print this message";
+
+pub enum Command {
+    Run(StartScreen),
+    SessionSave(String),
+    SessionRestore(String),
+    Organize { dry_run: bool },
+    Jump(char),
+    Minimize,
+    Restore,
+    Help,
+}
+
+pub fn parse(args: &[String]) -> Result<Command> {
+    match args {
+        [] => Ok(Command::Run(StartScreen::Tables)),
+        [flag] if flag == "--mru" => Ok(Command::Run(StartScreen::Mru)),
+        [flag] if flag == "-h" || flag == "--help" => Ok(Command::Help),
+        [command] if command == "minimize" => Ok(Command::Minimize),
+        [command] if command == "restore" => Ok(Command::Restore),
+        [command] if command == "organize" => Ok(Command::Organize { dry_run: false }),
+        [command, flag] if command == "organize" && flag == "--dry-run" => {
+            Ok(Command::Organize { dry_run: true })
+        }
+        [command, letter] if command == "jump" => match letter.chars().collect::<Vec<_>>()[..] {
+            [letter] => Ok(Command::Jump(letter)),
+            _ => bail!("Marks are a single letter, got: {}", letter),
+        },
+        [command, action, name] if command == "session" => match action.as_str() {
+            "save" => Ok(Command::SessionSave(name.clone())),
+            "restore" => Ok(Command::SessionRestore(name.clone())),
+            _ => bail!("Unknown session action: {}\n\n{}", action, USAGE),
+        },
+        _ => bail!("Unknown arguments: {}\n\n{}", args.join(" "), USAGE),
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::app::columns::{ClientColumn, WorkspaceColumn};
+
+/// User settings read from `$XDG_CONFIG_HOME/wmt/config.json`.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct Config {
+    pub preselect: Preselect,
+    pub rules: Vec<Rule>,
+    /// Which columns the client tables show, in order.
+    pub client_columns: Vec<ClientColumn>,
+    /// Which columns the workspace tables show, in order.
+    pub workspace_columns: Vec<WorkspaceColumn>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            preselect: Preselect::default(),
+            rules: Vec::new(),
+            client_columns: ClientColumn::DEFAULT.to_vec(),
+            workspace_columns: WorkspaceColumn::DEFAULT.to_vec(),
+        }
+    }
+}
+
+/// Sends clients matching `class` and/or `title` to `workspace` when
+/// organizing. Rules are tried in order and the first match wins.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Rule {
+    /// Matched exactly against the client class.
+    pub class: Option<String>,
+    /// A regex searched for in the client title.
+    pub title: Option<String>,
+    /// Anything `movetoworkspacesilent` accepts, e.g. `2` or `special:chat`.
+    pub workspace: String,
+}
+
+/// Which row the cursor starts on when a table opens.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Preselect {
+    /// The active workspace and the window that was focused before wmt.
+    #[default]
+    Active,
+    /// The workspace and window visited before the active ones.
+    Previous,
+}
+
+impl Config {
+    /// Loads the config file, falling back to the defaults when there is none.
+    pub fn load() -> Result<Config> {
+        let path = config_dir()?.join("config.json");
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Reading {}", path.display()));
+            }
+        };
+
+        serde_json::from_str(&contents).with_context(|| format!("Parsing {}", path.display()))
+    }
+}
+
+fn config_dir() -> Result<PathBuf> {
+    let base = match env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var("HOME").context("Failed to find $HOME")?).join(".config"),
+    };
+
+    Ok(base.join("wmt"))
+}
@@ -1,49 +1,244 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::fs;
 use std::io::{Read, Write};
 use std::os::unix::net::UnixStream;
 
 pub struct Hypr {
+    pub monitors: Vec<Monitor>,
     pub workspaces: Vec<Workspace>,
+    /// Every client, except the terminal wmt itself is running in when
+    /// built with [`Hypr::without_own_window`].
     pub clients: Vec<Client>,
+    pub active_workspace: i32,
+    /// The terminal wmt is running in, if it was left out and could be found.
+    pub own_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Monitor {
+    pub id: i32,
+    pub name: String,
+    pub width: i32,
+    pub height: i32,
+    pub x: i32,
+    pub y: i32,
+    pub scale: f32,
+    pub transform: i32,
+}
+
+impl Monitor {
+    /// Size in layout pixels, the unit client positions and sizes use.
+    pub fn logical_size(&self) -> (i32, i32) {
+        let width = (self.width as f32 / self.scale).round() as i32;
+        let height = (self.height as f32 / self.scale).round() as i32;
+        // Odd transforms rotate the monitor by 90 or 270 degrees
+        if self.transform % 2 == 1 {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Workspace {
-    pub id: u32,
+    pub id: i32,
     pub name: String,
     #[serde(rename = "monitorID")]
-    pub monitor_id: u32,
+    pub monitor_id: i32,
     pub windows: u32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Workspace {
+    /// How dispatchers refer to this workspace, see [`workspace_target`].
+    pub fn target(&self) -> String {
+        workspace_target(self.id, &self.name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ClientWorkspace {
-    pub id: u32,
+    pub id: i32,
     pub name: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl ClientWorkspace {
+    /// How dispatchers refer to this workspace, see [`workspace_target`].
+    pub fn target(&self) -> String {
+        workspace_target(self.id, &self.name)
+    }
+}
+
+/// How dispatchers refer to workspace `id` called `name`. Special and named
+/// workspaces both get negative ids that can't be targeted, so they go by
+/// name instead, which for special ones already has its `special:` prefix.
+fn workspace_target(id: i32, name: &str) -> String {
+    if id >= 0 {
+        id.to_string()
+    } else if name.starts_with("special:") {
+        name.to_string()
+    } else {
+        format!("name:{}", name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Client {
     pub class: String,
     pub title: String,
     pub workspace: ClientWorkspace,
     pub address: String,
+    pub pid: i32,
+    pub floating: bool,
+    pub at: [i32; 2],
+    pub size: [i32; 2],
+
+    #[serde(rename = "initialClass")]
+    pub initial_class: String,
+    #[serde(rename = "initialTitle")]
+    pub initial_title: String,
+    /// Addresses of every window in this client's group, in tab order.
+    /// Empty when the client isn't grouped.
+    pub grouped: Vec<String>,
+    /// Set on the members of a group other than the tab on show.
+    #[serde(default)]
+    pub hidden: bool,
 
     #[serde(rename = "focusHistoryID")]
     pub focus_id: u32,
 }
 
+#[cfg(test)]
+impl Client {
+    /// A tiled, ungrouped client on workspace 1, for tests to adjust.
+    pub fn for_test(address: &str, class: &str, title: &str) -> Client {
+        Client {
+            class: class.to_string(),
+            title: title.to_string(),
+            workspace: ClientWorkspace {
+                id: 1,
+                name: "1".to_string(),
+            },
+            address: address.to_string(),
+            pid: 0,
+            floating: false,
+            at: [0, 0],
+            size: [0, 0],
+            initial_class: class.to_string(),
+            initial_title: title.to_string(),
+            grouped: Vec::new(),
+            hidden: false,
+            focus_id: 0,
+        }
+    }
+}
+
+/// What was focused before wmt started changing things, so it can be put back.
+#[derive(Clone, Debug)]
+pub struct FocusState {
+    /// See [`Workspace::target`].
+    pub workspace: String,
+    pub window_address: Option<String>,
+    /// The terminal window wmt is running in, if it could be found.
+    pub own_address: Option<String>,
+}
+
+impl FocusState {
+    /// `hypr` should come from [`Hypr::without_own_window`]. wmt's own
+    /// terminal has usually taken focus by now, in which case the window
+    /// focused before it is the one to go back to.
+    pub fn capture(hypr: &Hypr) -> FocusState {
+        let workspace = hypr
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.id == hypr.active_workspace)
+            .map_or_else(|| hypr.active_workspace.to_string(), Workspace::target);
+        FocusState {
+            workspace,
+            window_address: hypr.active_client(),
+            own_address: hypr.own_address.clone(),
+        }
+    }
+
+    /// Focuses a client so it shows on screen, then hands focus back to wmt.
+    pub fn peek(&self, client_address: &str) -> Result<()> {
+        let Some(own_address) = &self.own_address else {
+            bail!("Can not peek without knowing which window wmt is running in");
+        };
+        dispatch_batch(&[
+            format!("focuswindow address:{}", client_address),
+            format!("focuswindow address:{}", own_address),
+        ])
+    }
+
+    pub fn restore(&self) -> Result<()> {
+        let mut dispatches = vec![format!("workspace {}", self.workspace)];
+        if let Some(window_address) = &self.window_address {
+            dispatches.push(format!("focuswindow address:{}", window_address));
+        }
+
+        dispatch_batch(&dispatches)
+    }
+}
+
 impl Hypr {
+    /// Everything as Hyprland reports it, for commands run from a shell or
+    /// a bind.
     pub fn new() -> Result<Hypr> {
-        let workspaces = get_workspaces()?;
-        let clients = get_clients()?;
         Ok(Hypr {
-            workspaces,
-            clients,
+            monitors: get_monitors()?,
+            workspaces: get_workspaces()?,
+            clients: get_clients()?,
+            active_workspace: get_active_workspace()?.id,
+            own_address: None,
         })
     }
+
+    /// Like [`Hypr::new`], leaving out the terminal wmt is running in so the
+    /// TUI doesn't list itself.
+    pub fn without_own_window() -> Result<Hypr> {
+        let mut hypr = Hypr::new()?;
+        hypr.own_address = own_client_address(&hypr.clients);
+        if let Some(own_address) = &hypr.own_address {
+            if let Some(index) = hypr.clients.iter().position(|c| c.address == *own_address) {
+                let own_client = hypr.clients.remove(index);
+                hypr.workspaces
+                    .iter_mut()
+                    .filter(|workspace| workspace.id == own_client.workspace.id)
+                    .for_each(|workspace| workspace.windows -= 1);
+            }
+        }
+
+        Ok(hypr)
+    }
+
+    /// Clients ordered from the most to the least recently focused.
+    fn clients_by_focus(&self) -> Vec<&Client> {
+        let mut clients: Vec<&Client> = self.clients.iter().collect();
+        clients.sort_by_key(|client| client.focus_id);
+        clients
+    }
+
+    /// The window that had focus before wmt's own terminal took it.
+    pub fn active_client(&self) -> Option<String> {
+        let client = *self.clients_by_focus().first()?;
+        Some(client.address.clone())
+    }
+
+    pub fn previous_client(&self) -> Option<String> {
+        let client = *self.clients_by_focus().get(1)?;
+        Some(client.address.clone())
+    }
+
+    /// The workspace of the most recently focused window outside the active one.
+    pub fn previous_workspace(&self) -> Option<i32> {
+        self.clients_by_focus()
+            .iter()
+            .map(|client| client.workspace.id)
+            .find(|id| *id != self.active_workspace)
+    }
 }
 
 fn connect() -> Result<UnixStream> {
@@ -57,7 +252,7 @@ fn connect() -> Result<UnixStream> {
     Ok(stream)
 }
 
-fn send_cmd(cmd: &str) -> Result<String> {
+pub fn send_cmd(cmd: &str) -> Result<String> {
     let mut stream = connect()?;
     stream
         .write_all(cmd.as_bytes())
@@ -70,11 +265,55 @@ fn send_cmd(cmd: &str) -> Result<String> {
     Ok(response)
 }
 
+/// Separator Hyprland places between the replies of a `[[BATCH]]` request.
+const BATCH_DELIMITER: &str = "\n\n\n";
+
+/// Sends several requests over a single connection as one `[[BATCH]]`
+/// request and returns the reply of each one, in order.
+fn send_batch(cmds: &[String]) -> Result<Vec<String>> {
+    if cmds.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batch = format!("[[BATCH]]{}", cmds.join(";"));
+    let response = send_cmd(&batch)?;
+    let replies: Vec<String> = response
+        .split(BATCH_DELIMITER)
+        .map(str::to_string)
+        .collect();
+    if replies.len() != cmds.len() {
+        bail!(
+            "Expected {} replies from hyprctl {}, got: \n\t{}",
+            cmds.len(),
+            batch,
+            response
+        );
+    }
+
+    Ok(replies)
+}
+
+/// Runs every dispatcher in one batch, failing if any of them was rejected.
+fn dispatch_batch(dispatches: &[String]) -> Result<()> {
+    let cmds: Vec<String> = dispatches
+        .iter()
+        .map(|dispatch| format!("dispatch {}", dispatch))
+        .collect();
+    let replies = send_batch(&cmds)?;
+    for (cmd, reply) in cmds.iter().zip(replies) {
+        if reply.trim() != "ok" {
+            bail!("Error running hyprctl {}: {}", cmd, reply.trim());
+        }
+    }
+
+    Ok(())
+}
+
 fn get_clients() -> Result<Vec<Client>> {
     let res = send_cmd("j/clients")?;
     let mut clients: Vec<Client> =
         serde_json::from_str(&res).context(format!("Parsing client data: \n\t{}", res))?;
-    clients.sort_by(|a, b| a.workspace.id.cmp(&b.workspace.id));
+    clients.sort_by_key(|client| client.workspace.id);
 
     Ok(clients)
 }
@@ -83,28 +322,234 @@ fn get_workspaces() -> Result<Vec<Workspace>> {
     let res = send_cmd("j/workspaces")?;
     let mut workspaces: Vec<Workspace> =
         serde_json::from_str(&res).context(format!("Parsing workspace data: \n\t{}", res))?;
-    workspaces.sort_by(|a, b| a.id.cmp(&b.id));
+    workspaces.sort_by_key(|workspace| workspace.id);
 
     Ok(workspaces)
 }
 
-pub fn switch_to_workspace(id: u32, focus_client: Option<String>) -> Result<()> {
-    let cmd = format!("dispatch workspace {}", id);
-    send_cmd(&cmd)?;
+fn get_monitors() -> Result<Vec<Monitor>> {
+    let res = send_cmd("j/monitors")?;
+    serde_json::from_str(&res).context(format!("Parsing monitor data: \n\t{}", res))
+}
+
+fn get_active_workspace() -> Result<Workspace> {
+    let res = send_cmd("j/activeworkspace")?;
+    serde_json::from_str(&res).context(format!("Parsing active workspace data: \n\t{}", res))
+}
+
+pub fn get_active_window() -> Result<Option<Client>> {
+    let res = send_cmd("j/activewindow")?;
+    if res.trim() == "{}" {
+        return Ok(None);
+    }
+    let client =
+        serde_json::from_str(&res).context(format!("Parsing active window data: \n\t{}", res))?;
+
+    Ok(Some(client))
+}
+
+/// Finds the window wmt runs in by walking up its process tree until a pid
+/// owns one of the clients, usually the terminal emulator.
+fn own_client_address(clients: &[Client]) -> Option<String> {
+    let mut pid = std::process::id() as i32;
+    while pid > 1 {
+        if let Some(client) = clients.iter().find(|client| client.pid == pid) {
+            return Some(client.address.clone());
+        }
+        pid = parent_pid(pid)?;
+    }
+
+    None
+}
+
+fn parent_pid(pid: i32) -> Option<i32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name can contain spaces, so skip past its closing paren
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// `workspace` is a dispatcher target, see [`Workspace::target`].
+pub fn switch_to_workspace(workspace: &str, focus_client: Option<String>) -> Result<()> {
+    let mut dispatches = vec![format!("workspace {}", workspace)];
     if let Some(client_address) = focus_client {
-        let cmd = format!("dispatch focuswindow address:{}", client_address);
-        send_cmd(&cmd)?;
+        dispatches.push(format!("focuswindow address:{}", client_address));
     }
 
-    Ok(())
+    dispatch_batch(&dispatches)
+}
+
+/// Focuses a client, switching to its workspace if needed.
+pub fn focus_client(client_address: &str) -> Result<()> {
+    dispatch_batch(&[format!("focuswindow address:{}", client_address)])
 }
 
-pub fn send_to_workspace(workspace: u32, client_address: String) -> Result<()> {
-    let cmd = format!(
-        "dispatch movetoworkspacesilent {},address:{}",
-        workspace, client_address
-    );
-    send_cmd(&cmd)?;
+/// Runs a dispatcher that only acts on the focused window against
+/// `client_address`, then hands focus back to `return_to`, all in one batch.
+fn dispatch_on_client(client_address: &str, dispatch: &str, return_to: Option<&str>) -> Result<()> {
+    let mut dispatches = vec![
+        format!("focuswindow address:{}", client_address),
+        dispatch.to_string(),
+    ];
+    if let Some(return_to) = return_to {
+        dispatches.push(format!("focuswindow address:{}", return_to));
+    }
 
-    Ok(())
+    dispatch_batch(&dispatches)
+}
+
+/// Turns a window into a group, or ungroups the group it is in.
+pub fn toggle_group(client_address: &str, return_to: Option<&str>) -> Result<()> {
+    dispatch_on_client(client_address, "togglegroup", return_to)
+}
+
+/// Moves a window into the group next to it in `direction`, one of `l`, `r`,
+/// `u` or `d`.
+pub fn move_into_group(
+    client_address: &str,
+    direction: char,
+    return_to: Option<&str>,
+) -> Result<()> {
+    let dispatch = format!("moveintogroup {}", direction);
+    dispatch_on_client(client_address, &dispatch, return_to)
+}
+
+pub fn move_out_of_group(client_address: &str) -> Result<()> {
+    dispatch_batch(&[format!("moveoutofgroup address:{}", client_address)])
+}
+
+/// Shows the tab after or before the one on show in the group a window is
+/// in. `changegroupactive` would go from whichever window it was run on, so
+/// this focuses the neighbouring tab instead, which also puts it on show.
+pub fn change_group_active(
+    client_address: &str,
+    clients: &[Client],
+    forward: bool,
+    return_to: Option<&str>,
+) -> Result<()> {
+    let group = clients
+        .iter()
+        .find(|client| client.address == client_address)
+        .map(|client| &client.grouped)
+        .filter(|group| !group.is_empty())
+        .context("The window isn't in a group")?;
+    let active = group
+        .iter()
+        .position(|address| {
+            clients
+                .iter()
+                .any(|client| &client.address == address && !client.hidden)
+        })
+        .unwrap_or(0);
+    let next = if forward {
+        (active + 1) % group.len()
+    } else {
+        (active + group.len() - 1) % group.len()
+    };
+
+    let mut dispatches = vec![format!("focuswindow address:{}", group[next])];
+    if let Some(return_to) = return_to {
+        dispatches.push(format!("focuswindow address:{}", return_to));
+    }
+    dispatch_batch(&dispatches)
+}
+
+/// Asks a window to close, the same as its close button would.
+pub fn close_client(client_address: &str) -> Result<()> {
+    dispatch_batch(&[format!("closewindow address:{}", client_address)])
+}
+
+pub fn toggle_floating(client_address: &str) -> Result<()> {
+    dispatch_batch(&[format!("togglefloating address:{}", client_address)])
+}
+
+pub fn toggle_fullscreen(client_address: &str, return_to: Option<&str>) -> Result<()> {
+    dispatch_on_client(client_address, "fullscreen 0", return_to)
+}
+
+/// Shows or hides a special workspace, named without the `special:` prefix.
+pub fn toggle_special_workspace(name: &str) -> Result<()> {
+    dispatch_batch(&[format!("togglespecialworkspace {}", name)])
+}
+
+pub fn send_to_workspace(workspace: &str, client_address: String) -> Result<()> {
+    send_many_to_workspaces(&[(workspace.to_string(), client_address)])
+}
+
+/// Brings a client onto the workspace on screen and focuses it.
+pub fn pull_to_active_workspace(client_address: &str) -> Result<()> {
+    let workspace = get_active_workspace()?;
+    follow_to_workspace(&workspace.target(), client_address)
+}
+
+/// Moves a client to a workspace and follows it there, unlike the silent
+/// [`send_to_workspace`]. The workspace can be anything `movetoworkspace`
+/// accepts, e.g. `3` or `empty`.
+pub fn follow_to_workspace(workspace: &str, client_address: &str) -> Result<()> {
+    dispatch_batch(&[
+        format!("movetoworkspace {},address:{}", workspace, client_address),
+        format!("focuswindow address:{}", client_address),
+    ])
+}
+
+/// Moves every `(workspace, client address)` pair silently in a single batch,
+/// so bulk moves, swaps and restores don't flicker through intermediate states.
+/// Workspaces can be anything `movetoworkspacesilent` accepts, e.g. `3` or
+/// `special:chat`.
+pub fn send_many_to_workspaces(moves: &[(String, String)]) -> Result<()> {
+    let dispatches: Vec<String> = moves
+        .iter()
+        .map(|(workspace, client_address)| {
+            format!(
+                "movetoworkspacesilent {},address:{}",
+                workspace, client_address
+            )
+        })
+        .collect();
+
+    dispatch_batch(&dispatches)
+}
+
+/// Position and size of a floating window, in layout pixels.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Geometry {
+    pub at: [i32; 2],
+    pub size: [i32; 2],
+}
+
+/// Where a client should end up, see [`place_clients`].
+pub struct Placement {
+    pub address: String,
+    /// Anything `movetoworkspacesilent` accepts, e.g. `3` or `special:chat`.
+    pub workspace: String,
+    /// Floats the client at this geometry, or tiles it when `None`.
+    pub floating: Option<Geometry>,
+}
+
+/// Moves clients to their workspaces and floats or tiles them in one batch.
+pub fn place_clients(placements: &[Placement]) -> Result<()> {
+    let mut dispatches = Vec::new();
+    for placement in placements {
+        let address = &placement.address;
+        dispatches.push(format!(
+            "movetoworkspacesilent {},address:{}",
+            placement.workspace, address
+        ));
+        match placement.floating {
+            Some(geometry) => {
+                dispatches.push(format!("setfloating address:{}", address));
+                dispatches.push(format!(
+                    "resizewindowpixel exact {} {},address:{}",
+                    geometry.size[0], geometry.size[1], address
+                ));
+                dispatches.push(format!(
+                    "movewindowpixel exact {} {},address:{}",
+                    geometry.at[0], geometry.at[1], address
+                ));
+            }
+            None => dispatches.push(format!("settiled address:{}", address)),
+        }
+    }
+
+    dispatch_batch(&dispatches)
 }
@@ -1,14 +1,109 @@
 mod app;
+mod cli;
+mod config;
 mod hypr;
+mod marks;
+mod minimize;
+mod organize;
 mod screen;
+mod session;
+mod state;
 
-use anyhow::Result;
+use std::io::stdout;
+
+use anyhow::{Context, Result};
 use app::App;
+use cli::Command;
+use config::Config;
+use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+    terminal::supports_keyboard_enhancement,
+};
+use hypr::Hypr;
 
 fn main() -> Result<()> {
-    let mut terminal = ratatui::init();
-    let mut app = App::new()?;
-    let app_result = app.run(&mut terminal);
-    ratatui::restore();
-    app_result
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse(&args)? {
+        Command::Run(start_screen) => {
+            let mut terminal = ratatui::init();
+            let enhanced_keyboard = enable_key_release_events();
+            let _ = execute!(stdout(), EnableMouseCapture);
+            restore_input_modes_on_panic(enhanced_keyboard);
+            let app_result = App::new(start_screen).and_then(|mut app| app.run(&mut terminal));
+            let _ = execute!(stdout(), DisableMouseCapture);
+            if enhanced_keyboard {
+                let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
+            }
+            ratatui::restore();
+            app_result
+        }
+        Command::SessionSave(name) => {
+            let saved = session::save(&name)?;
+            println!("Saved {} windows to session {}", saved, name);
+            Ok(())
+        }
+        Command::SessionRestore(name) => {
+            let summary = session::restore(&name)?;
+            println!(
+                "Restored {} of {} saved windows from session {}",
+                summary.restored, summary.saved, name
+            );
+            Ok(())
+        }
+        Command::Organize { dry_run } => {
+            let config = Config::load().context("Loading config")?;
+            let hypr = Hypr::new().context("Connecting to Hyprland")?;
+            let moves = organize::plan(&config.rules, &hypr.clients)?;
+            for planned in &moves {
+                println!(
+                    "{} ({}): {} -> {}",
+                    planned.class, planned.title, planned.from.name, planned.to
+                );
+            }
+            if !dry_run {
+                organize::apply(&moves).context("Moving clients")?;
+            }
+            Ok(())
+        }
+        Command::Jump(letter) => marks::jump(letter),
+        Command::Minimize => minimize::minimize_active(),
+        Command::Restore => minimize::restore_last(),
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Chains onto the panic hook ratatui installs, so a panic doesn't leave the
+/// terminal reporting mouse events or key releases to the shell.
+fn restore_input_modes_on_panic(enhanced_keyboard: bool) {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = execute!(stdout(), DisableMouseCapture);
+        if enhanced_keyboard {
+            let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
+        }
+        hook(info);
+    }));
+}
+
+/// Asks the terminal to report key releases, including bare modifier keys, so
+/// letting go of a held modifier can confirm a selection. Terminals without
+/// the kitty keyboard protocol keep sending plain presses. Alternate keys are
+/// needed so shifted characters like `?` still arrive as themselves.
+fn enable_key_release_events() -> bool {
+    if !supports_keyboard_enhancement().unwrap_or(false) {
+        return false;
+    }
+
+    let flags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+        | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+        | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+        | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES;
+    execute!(stdout(), PushKeyboardEnhancementFlags(flags)).is_ok()
 }
@@ -0,0 +1,53 @@
+use anyhow::{bail, Context, Result};
+
+use crate::hypr::{self, Client, Hypr};
+use crate::state::{Mark, State};
+
+/// Remembers `client` under `letter`, replacing any previous mark.
+pub fn set(letter: char, client: &Client) -> Result<()> {
+    if !letter.is_ascii_alphabetic() {
+        bail!("Marks must be letters, got {:?}", letter);
+    }
+
+    let mut state = State::load()?;
+    state.marks.insert(
+        letter,
+        Mark {
+            address: client.address.clone(),
+            class: client.class.clone(),
+            initial_title: client.initial_title.clone(),
+        },
+    );
+    state.save()
+}
+
+/// Focuses the window marked with `letter`.
+pub fn jump(letter: char) -> Result<()> {
+    let mut state = State::load()?;
+    let Some(mark) = state.marks.get_mut(&letter) else {
+        bail!("No window is marked {}", letter);
+    };
+    let hypr = Hypr::new().context("Connecting to Hyprland")?;
+    let Some(client) = find(mark, &hypr.clients) else {
+        bail!("The window marked {} is gone", letter);
+    };
+
+    // Remember the new address if the window had to be found again
+    if client.address != mark.address {
+        mark.address = client.address.clone();
+        state.save()?;
+    }
+
+    hypr::focus_client(&client.address)
+}
+
+fn find<'a>(mark: &Mark, clients: &'a [Client]) -> Option<&'a Client> {
+    clients
+        .iter()
+        .find(|client| client.address == mark.address)
+        .or_else(|| {
+            clients.iter().find(|client| {
+                client.class == mark.class && client.initial_title == mark.initial_title
+            })
+        })
+}
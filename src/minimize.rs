@@ -0,0 +1,109 @@
+use anyhow::{bail, Context, Result};
+
+use crate::hypr::{self, Client, ClientWorkspace, Hypr};
+use crate::state::{Minimized, State};
+
+/// Hyprland has no minimize, so minimized windows are parked here.
+pub const MINIMIZED_WORKSPACE: &str = "special:minimized";
+
+/// Hides `client` in the minimized workspace, remembering where it was.
+pub fn minimize(client: &Client) -> Result<()> {
+    if client.workspace.name == MINIMIZED_WORKSPACE {
+        bail!("{} is already minimized", client.class);
+    }
+
+    hypr::send_many_to_workspaces(&[(MINIMIZED_WORKSPACE.to_string(), client.address.clone())])?;
+
+    remember(&client.address, &client.workspace)
+}
+
+/// Records that `client_address` was minimized from `origin`, so restoring
+/// it puts it back there.
+pub fn remember(client_address: &str, origin: &ClientWorkspace) -> Result<()> {
+    let mut state = State::load()?;
+    state.minimized.retain(|m| m.address != client_address);
+    state.minimized.push(Minimized {
+        address: client_address.to_string(),
+        origin: origin.clone(),
+    });
+    state.save()
+}
+
+/// Drops what was remembered about `client_address` being minimized, for
+/// when it was put back some other way.
+pub fn forget(client_address: &str) -> Result<()> {
+    let mut state = State::load()?;
+    state.minimized.retain(|m| m.address != client_address);
+    state.save()
+}
+
+/// Moves a minimized client back to the workspace it was minimized from, or
+/// to `fallback` when that isn't known.
+pub fn restore(client_address: &str, fallback: i32) -> Result<ClientWorkspace> {
+    let mut state = State::load()?;
+    let origin = state
+        .minimized
+        .iter()
+        .find(|m| m.address == client_address)
+        .map(|m| m.origin.clone())
+        .unwrap_or(ClientWorkspace {
+            id: fallback,
+            name: fallback.to_string(),
+        });
+
+    hypr::send_many_to_workspaces(&[(origin.target(), client_address.to_string())])?;
+
+    state.minimized.retain(|m| m.address != client_address);
+    state.save()?;
+
+    Ok(origin)
+}
+
+/// The clients in the minimized workspace, most recently minimized first,
+/// with their workspace set to where they will be restored to.
+pub fn minimized_clients(hypr: &Hypr) -> Result<Vec<Client>> {
+    let state = State::load()?;
+    let mut clients: Vec<Client> = hypr
+        .clients
+        .iter()
+        .filter(|client| client.workspace.name == MINIMIZED_WORKSPACE)
+        .cloned()
+        .collect();
+    for client in clients.iter_mut() {
+        if let Some(minimized) = state.minimized.iter().find(|m| m.address == client.address) {
+            client.workspace = minimized.origin.clone();
+        }
+    }
+    // Windows minimized outside of wmt have no entry, so they sort last
+    let position = |client: &Client| {
+        state
+            .minimized
+            .iter()
+            .rposition(|m| m.address == client.address)
+            .map_or(0, |i| i + 1)
+    };
+    clients.sort_by_key(|client| std::cmp::Reverse(position(client)));
+
+    Ok(clients)
+}
+
+/// Minimizes the focused window, for binds.
+pub fn minimize_active() -> Result<()> {
+    let Some(client) = hypr::get_active_window()? else {
+        bail!("No window is focused");
+    };
+
+    minimize(&client)
+}
+
+/// Restores and focuses the most recently minimized window, for binds.
+pub fn restore_last() -> Result<()> {
+    let hypr = Hypr::new().context("Connecting to Hyprland")?;
+    let clients = minimized_clients(&hypr)?;
+    let Some(client) = clients.first() else {
+        bail!("No window is minimized");
+    };
+
+    restore(&client.address, hypr.active_workspace)?;
+    hypr::focus_client(&client.address)
+}
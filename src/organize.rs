@@ -0,0 +1,184 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+
+use crate::config::Rule;
+use crate::hypr::{self, Client, ClientWorkspace};
+
+/// A client that a rule wants on another workspace.
+pub struct Move {
+    pub address: String,
+    pub class: String,
+    pub title: String,
+    pub from: ClientWorkspace,
+    pub to: String,
+}
+
+struct CompiledRule<'a> {
+    rule: &'a Rule,
+    title: Option<Regex>,
+}
+
+impl CompiledRule<'_> {
+    fn matches(&self, client: &Client) -> bool {
+        let class_matches = self
+            .rule
+            .class
+            .as_ref()
+            .is_none_or(|class| *class == client.class);
+        let title_matches = self
+            .title
+            .as_ref()
+            .is_none_or(|title| title.is_match(&client.title));
+
+        class_matches && title_matches
+    }
+}
+
+/// Works out which clients are not where the first matching rule wants them.
+pub fn plan(rules: &[Rule], clients: &[Client]) -> Result<Vec<Move>> {
+    let mut compiled = Vec::new();
+    for rule in rules {
+        if rule.class.is_none() && rule.title.is_none() {
+            bail!(
+                "Rule for workspace {} needs a class or a title",
+                rule.workspace
+            );
+        }
+        let title = match &rule.title {
+            Some(title) => {
+                Some(Regex::new(title).with_context(|| format!("Parsing title rule {}", title))?)
+            }
+            None => None,
+        };
+        compiled.push(CompiledRule { rule, title });
+    }
+
+    let moves = clients
+        .iter()
+        .filter_map(|client| {
+            let rule = compiled.iter().find(|rule| rule.matches(client))?.rule;
+            if is_on_workspace(client, &rule.workspace) {
+                return None;
+            }
+            Some(Move {
+                address: client.address.clone(),
+                class: client.class.clone(),
+                title: client.title.clone(),
+                from: client.workspace.clone(),
+                to: rule.workspace.clone(),
+            })
+        })
+        .collect();
+
+    Ok(moves)
+}
+
+/// Runs every move in a single batch.
+pub fn apply(moves: &[Move]) -> Result<()> {
+    let moves: Vec<(String, String)> = moves
+        .iter()
+        .map(|planned| (planned.to.clone(), planned.address.clone()))
+        .collect();
+
+    hypr::send_many_to_workspaces(&moves)
+}
+
+fn is_on_workspace(client: &Client, workspace: &str) -> bool {
+    let current = &client.workspace;
+    workspace == current.target()
+        || workspace == current.name
+        || workspace.strip_prefix("name:") == Some(current.name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(class: Option<&str>, title: Option<&str>, workspace: &str) -> Rule {
+        Rule {
+            class: class.map(str::to_string),
+            title: title.map(str::to_string),
+            workspace: workspace.to_string(),
+        }
+    }
+
+    fn on_workspace(mut client: Client, id: i32, name: &str) -> Client {
+        client.workspace = ClientWorkspace {
+            id,
+            name: name.to_string(),
+        };
+        client
+    }
+
+    fn destinations(moves: &[Move]) -> Vec<(&str, &str)> {
+        moves
+            .iter()
+            .map(|planned| (planned.address.as_str(), planned.to.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn the_first_matching_rule_wins() {
+        let rules = [
+            rule(Some("firefox"), Some("Meet"), "4"),
+            rule(Some("firefox"), None, "2"),
+        ];
+        let clients = [
+            Client::for_test("0x1", "firefox", "Google Meet"),
+            Client::for_test("0x2", "firefox", "Docs"),
+            Client::for_test("0x3", "kitty", "Meet"),
+        ];
+
+        let moves = plan(&rules, &clients).unwrap();
+        assert_eq!(destinations(&moves), [("0x1", "4"), ("0x2", "2")]);
+    }
+
+    #[test]
+    fn titles_are_searched_as_regexes() {
+        let rules = [rule(None, Some("^(Slack|Discord) "), "special:chat")];
+        let clients = [
+            Client::for_test("0x1", "slack", "Slack | general"),
+            Client::for_test("0x2", "discord", "Discord #rust"),
+            Client::for_test("0x3", "kitty", "vim Slack notes"),
+        ];
+
+        let moves = plan(&rules, &clients).unwrap();
+        assert_eq!(
+            destinations(&moves),
+            [("0x1", "special:chat"), ("0x2", "special:chat")]
+        );
+    }
+
+    #[test]
+    fn clients_already_in_place_stay() {
+        let rules = [
+            rule(Some("a"), None, "3"),
+            rule(Some("b"), None, "web"),
+            rule(Some("c"), None, "name:web"),
+            rule(Some("d"), None, "special:chat"),
+        ];
+        let clients = [
+            on_workspace(Client::for_test("0x1", "a", ""), 3, "3"),
+            on_workspace(Client::for_test("0x2", "b", ""), -1337, "web"),
+            on_workspace(Client::for_test("0x3", "c", ""), -1337, "web"),
+            on_workspace(Client::for_test("0x4", "d", ""), -98, "special:chat"),
+        ];
+
+        assert!(plan(&rules, &clients).unwrap().is_empty());
+    }
+
+    #[test]
+    fn moves_remember_where_clients_were() {
+        let rules = [rule(Some("a"), None, "2")];
+        let clients = [on_workspace(Client::for_test("0x1", "a", ""), -1337, "web")];
+
+        let moves = plan(&rules, &clients).unwrap();
+        assert_eq!(moves[0].from.target(), "name:web");
+    }
+
+    #[test]
+    fn bad_rules_are_refused() {
+        assert!(plan(&[rule(None, None, "2")], &[]).is_err());
+        assert!(plan(&[rule(None, Some("("), "2")], &[]).is_err());
+    }
+}
@@ -1,20 +1,62 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::widgets::WidgetRef;
 
+use crate::app::{command_line::Selection, history::Operation};
+
+pub mod console_screen;
 pub mod help_screen;
+pub mod history_screen;
+pub mod mru_screen;
+pub mod organize_screen;
+pub mod overview_screen;
+pub mod scratchpad_screen;
 pub mod send_workspace_screen;
 pub mod table_screen;
+pub mod workspace_screen;
 
 pub enum ScreenEvent {
     Close,
     AddScreen(Box<dyn Screen>),
     PopScreen,
     PopAndRefresh,
+    /// Adds an operation to the undo history and refreshes the screen.
+    Record(Operation),
+    /// Like `PopAndRefresh`, after adding an operation to the undo history.
+    PopAndRecord(Operation),
+    /// Reverts this many of the most recent operations.
+    Undo(usize),
+    /// Closes the history screen, then reverts this many operations.
+    PopAndUndo(usize),
+    ShowHistory,
+    /// Briefly focus a client so it can be seen, see [`crate::hypr::FocusState::peek`].
+    Peek(String),
+    /// Shows what went wrong in the status line.
+    Error(anyhow::Error),
 }
 
 pub trait ScreenWidget {
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent>;
     fn refresh(&mut self);
+
+    /// Whether the screen is waiting for a key that would otherwise be a
+    /// global binding, e.g. the letter after a mark prefix.
+    fn captures_input(&self) -> bool {
+        false
+    }
+
+    /// Only delivered by terminals that support the kitty keyboard protocol.
+    fn handle_key_release(&mut self, _key_event: KeyEvent) -> Option<ScreenEvent> {
+        None
+    }
+
+    fn handle_mouse_event(&mut self, _mouse_event: MouseEvent) -> Option<ScreenEvent> {
+        None
+    }
+
+    /// What the cursor is on, for the placeholders of `:` commands.
+    fn selection(&self) -> Selection {
+        Selection::default()
+    }
 }
 
 pub trait Screen: ScreenWidget + WidgetRef {}
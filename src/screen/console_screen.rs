@@ -0,0 +1,222 @@
+use std::cell::Cell;
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Margin, Rect},
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{
+        Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+        WidgetRef,
+    },
+};
+use serde::de::IgnoredAny;
+
+use crate::{
+    app::{
+        input_history::{self, InputHistory},
+        json,
+        text_input::TextInput,
+    },
+    hypr,
+    state::State,
+};
+
+use super::{Screen, ScreenEvent, ScreenWidget};
+
+/// Sends raw requests to Hyprland's socket, like running hyprctl, and shows
+/// the replies.
+pub struct ConsoleScreen {
+    input: TextInput,
+    history: InputHistory,
+    /// The last request sent, with the reply to it.
+    reply: Option<(String, Result<String>)>,
+    /// Why the request history couldn't be loaded or saved, shown under the
+    /// reply.
+    history_error: Option<anyhow::Error>,
+    /// Shows replies as Hyprland sent them instead of laid out and colored.
+    raw: bool,
+    /// The reply, ready to draw.
+    lines: Vec<Line<'static>>,
+    /// The first line on screen.
+    scroll: usize,
+    /// How many lines fit on screen, as of the last render.
+    page: Cell<usize>,
+}
+
+impl ConsoleScreen {
+    pub fn new() -> ConsoleScreen {
+        let (history, history_error) = match State::load() {
+            Ok(state) => (state.console_history, None),
+            Err(err) => (Vec::new(), Some(err.context("Loading the console history"))),
+        };
+        let mut console_screen = ConsoleScreen {
+            input: TextInput::default(),
+            history: InputHistory::new(history),
+            reply: None,
+            history_error,
+            raw: false,
+            lines: Vec::new(),
+            scroll: 0,
+            page: Cell::new(1),
+        };
+        console_screen.format_reply();
+        console_screen
+    }
+
+    fn send_request(&mut self) -> Option<ScreenEvent> {
+        let request = self.input.value().trim().to_string();
+        if request.is_empty() {
+            return None;
+        }
+        let reply = hypr::send_cmd(&request);
+        self.history.push(&request);
+        self.history_error = record(&request).context("Saving the console history").err();
+
+        self.input = TextInput::default();
+        self.reply = Some((request, reply));
+        self.scroll = 0;
+        self.format_reply();
+
+        None
+    }
+
+    fn toggle_raw(&mut self) -> Option<ScreenEvent> {
+        self.raw = !self.raw;
+        self.format_reply();
+
+        None
+    }
+
+    fn format_reply(&mut self) {
+        let mut lines = Vec::new();
+        if let Some((request, reply)) = &self.reply {
+            lines.push(Line::from(vec!["> ".blue().bold(), request.clone().into()]));
+            match reply {
+                Err(err) => lines.push(Line::from(format!("{:#}", err).red())),
+                Ok(reply) if !self.raw && serde_json::from_str::<IgnoredAny>(reply).is_ok() => {
+                    lines.extend(json::highlight(reply))
+                }
+                Ok(reply) => lines.extend(reply.lines().map(|line| Line::from(line.to_string()))),
+            }
+        }
+        if let Some(err) = &self.history_error {
+            lines.push(Line::from(format!("{:#}", err).red()));
+        }
+        self.lines = lines;
+    }
+
+    fn scroll_by(&mut self, lines: isize) -> Option<ScreenEvent> {
+        let max = self.lines.len().saturating_sub(self.page.get());
+        self.scroll = self.scroll.saturating_add_signed(lines).min(max);
+
+        None
+    }
+
+    fn history_move(&mut self, older: bool) -> Option<ScreenEvent> {
+        let request = if older {
+            self.history.older()?
+        } else {
+            self.history.newer()?
+        };
+        self.input.set_value(request.to_string());
+
+        None
+    }
+}
+
+/// Adds `request` to the history kept in the state file.
+fn record(request: &str) -> Result<()> {
+    let mut state = State::load()?;
+    input_history::add(&mut state.console_history, request);
+    state.save()
+}
+
+impl WidgetRef for ConsoleScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Send ".into(),
+            "<Enter>".blue().bold(),
+            " Raw ".into(),
+            "<Ctrl-r>".blue().bold(),
+            " Scroll ".into(),
+            "<PgUp/PgDn>".blue().bold(),
+            " Back ".into(),
+            "<ESC> ".blue().bold(),
+        ]);
+        let mut block = Block::bordered()
+            .title_top(" Console ")
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK);
+        if self.raw {
+            block = block.title_top(Line::from(" Raw ".green()).right_aligned());
+        }
+        block.render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let [reply_area, input_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        self.page.set((reply_area.height as usize).max(1));
+
+        if self.lines.is_empty() {
+            Paragraph::new("Type a request such as j/monitors or dispatch workspace 2".italic())
+                .alignment(Alignment::Center)
+                .render(reply_area, buf);
+        } else if self.lines.len() > reply_area.height as usize {
+            let [text_area, scrollbar_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(reply_area);
+            Paragraph::new(self.lines.clone())
+                .scroll((self.scroll as u16, 0))
+                .render(text_area, buf);
+            let mut scrollbar_state = ScrollbarState::new(self.lines.len())
+                .viewport_content_length(reply_area.height as usize)
+                .position(self.scroll);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(scrollbar_area, buf, &mut scrollbar_state);
+        } else {
+            Paragraph::new(self.lines.clone()).render(reply_area, buf);
+        }
+
+        self.input.render_ref(input_area, buf);
+    }
+}
+
+impl ScreenWidget for ConsoleScreen {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        let page = self.page.get() as isize;
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Enter => self.send_request(),
+            KeyCode::Char('r') if ctrl => self.toggle_raw(),
+            KeyCode::Up => self.history_move(true),
+            KeyCode::Down => self.history_move(false),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home => self.scroll_by(isize::MIN),
+            KeyCode::End => self.scroll_by(isize::MAX),
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+
+            _ if ctrl => None,
+            _ => {
+                self.input.handle_key_event(key_event);
+                None
+            }
+        }
+    }
+
+    fn refresh(&mut self) {}
+
+    /// Everything typed is part of a request.
+    fn captures_input(&self) -> bool {
+        true
+    }
+}
+impl Screen for ConsoleScreen {}
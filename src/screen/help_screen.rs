@@ -5,61 +5,205 @@ use ratatui::{
     style::Stylize,
     symbols::border,
     text::Line,
-    widgets::{Block, Paragraph, Row, Table, Widget, WidgetRef},
+    widgets::{Block, Paragraph, Row, StatefulWidget, Table, TableState, Widget, WidgetRef},
 };
 
 use super::{Screen, ScreenEvent, ScreenWidget};
 
-pub struct HelpScreen {}
+type Keybinds = (&'static str, &'static [(&'static str, &'static str)]);
+
+const MAIN_SCREEN: Keybinds = (
+    "Main Screen",
+    &[
+        ("Down", "j"),
+        ("Down", "Arrow Down"),
+        ("Up", "k"),
+        ("Up", "Arrow Up"),
+        ("Half page down/up", "Ctrl-d/Ctrl-u"),
+        ("Page down/up", "PgDn/PgUp"),
+        ("Move count rows", "<count>j/k"),
+        ("Top/bottom", "gg/G"),
+        ("Focus client", "Enter"),
+        ("Switch workspace", "g0-g9"),
+        ("Send to workspace", "s"),
+        ("Bring window here", "b"),
+        ("Send to scratchpad", "S"),
+        ("Scratchpads", "x"),
+        ("Minimize", "z"),
+        ("Switch screen/pane", "Tab"),
+        ("Previous screen", "Shift+Tab"),
+        ("Recent windows", "r"),
+        ("Workspace overview", "w"),
+        ("Hyprland console", "C"),
+        ("Filter by workspace", "f"),
+        ("Cycle sort", "c"),
+        ("Cycle grouping", "v"),
+        ("Peek at next in group", "Enter"),
+        ("Switch to peeked window", "Space"),
+        ("Open workspace", "l"),
+        ("Open workspace", "Arrow Right"),
+        ("Toggle peek", "p"),
+        ("Organize by rules", "o"),
+        ("Undo", "u"),
+        ("Undo history", "U"),
+        ("Mark window", "m<letter>"),
+        ("Jump to mark", "'<letter>"),
+        ("Toggle group", "t"),
+        ("Move into group", "i<hjkl>"),
+        ("Move out of group", "O"),
+        ("Previous group tab", "<"),
+        ("Next group tab", ">"),
+    ],
+);
+
+const MINIMIZED: Keybinds = ("Minimized Tab", &[("Restore minimized", "Enter")]);
+
+const SEND_WORKSPACE: Keybinds = (
+    "Send to Workspace Mode",
+    &[
+        ("Choose workspace", "Enter"),
+        ("Choose workspace", "g0-g9"),
+        ("Move count rows", "<count>j/k"),
+        ("Send and follow", "f"),
+        ("Send and follow", "Alt+Enter"),
+        ("Send and follow", "Alt+0-9"),
+        ("New workspace", "Enter on +"),
+    ],
+);
+
+const RECENT: Keybinds = (
+    "Recent Windows Mode",
+    &[
+        ("Next window", "Tab"),
+        ("Previous window", "Shift+Tab"),
+        ("Move count rows", "<count>j/k"),
+        ("Focus window", "Enter"),
+        ("Focus window", "Space"),
+        ("Toggle peek", "p"),
+    ],
+);
+
+const WORKSPACE: Keybinds = (
+    "Workspace Mode",
+    &[
+        ("Move count rows", "<count>j/k"),
+        ("Focus window", "Enter"),
+        ("Send to workspace", "s"),
+        ("Close window", "c, then y"),
+        ("Toggle floating", "v"),
+        ("Toggle fullscreen", "F"),
+        ("Back", "h"),
+        ("Back", "Arrow Left"),
+    ],
+);
+
+const OVERVIEW: Keybinds = (
+    "Overview Mode",
+    &[
+        ("Move", "hjkl"),
+        ("Move", "Arrow keys"),
+        ("Switch workspace", "Enter"),
+    ],
+);
+
+const SCRATCHPADS: Keybinds = (
+    "Scratchpads Mode",
+    &[
+        ("Toggle visible", "Enter"),
+        ("Toggle visible", "t"),
+        ("Pull out here", "p"),
+    ],
+);
+
+const SEND_SCRATCHPAD: Keybinds = (
+    "Send to Scratchpad Mode",
+    &[("Send to selected", "Enter"), ("Send to new", "n")],
+);
+
+const COMMAND_LINE: Keybinds = (
+    "Command Line",
+    &[
+        ("Open on any screen", ":"),
+        ("Complete", "Tab"),
+        ("Previous/next command", "Up/Down"),
+        ("Run", "Enter"),
+        ("Cancel", "Esc"),
+    ],
+);
+
+const CONSOLE: Keybinds = (
+    "Console Mode",
+    &[
+        ("Send request", "Enter"),
+        ("Previous/next request", "Up/Down"),
+        ("Toggle raw reply", "Ctrl-r"),
+        ("Scroll reply", "PgUp/PgDn"),
+        ("Back", "Esc"),
+    ],
+);
+
+const SECTIONS: &[Keybinds] = &[
+    MAIN_SCREEN,
+    MINIMIZED,
+    SEND_WORKSPACE,
+    RECENT,
+    WORKSPACE,
+    OVERVIEW,
+    SCRATCHPADS,
+    SEND_SCRATCHPAD,
+    COMMAND_LINE,
+    CONSOLE,
+];
+
+pub struct HelpScreen {
+    /// Scroll position, for terminals too short to fit every keybind.
+    state: TableState,
+}
 
 impl HelpScreen {
     pub fn new() -> HelpScreen {
-        HelpScreen {}
+        HelpScreen {
+            state: TableState::default(),
+        }
     }
 
-    fn render_keybinds(&self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Keybinds".blue())
-            .alignment(Alignment::Center)
-            .render(area, buf);
+    fn row_count() -> usize {
+        SECTIONS
+            .iter()
+            .map(|(_, keybinds)| keybinds.len() + 2)
+            .sum()
+    }
 
-        let area = area.offset(Offset { x: 0, y: 1 });
-        self.render_table_screen_keybinds(area, buf);
-        let area = area.offset(Offset { x: 0, y: 10 });
-        self.render_table_send_workspace(area, buf);
+    fn scroll_down(&mut self) -> Option<ScreenEvent> {
+        if self.state.offset() + 1 < Self::row_count() {
+            *self.state.offset_mut() += 1;
+        }
+
+        None
     }
 
-    fn render_table_screen_keybinds(&self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Main Screen".bold())
-            .alignment(Alignment::Left)
-            .render(area, buf);
-        let area = area.offset(Offset { x: 0, y: 1 });
-
-        let widths = [Constraint::Max(20), Constraint::Max(10)];
-        let rows = [
-            Row::new(vec!["Down", "j"]),
-            Row::new(vec!["Down", "Arrow Down"]),
-            Row::new(vec!["Up", "k"]),
-            Row::new(vec!["Up", "Arrow Up"]),
-            Row::new(vec!["Focus client", "Enter"]),
-            Row::new(vec!["Switch workspace", "0-9"]),
-            Row::new(vec!["Send to workspace", "s"]),
-            Row::new(vec!["Switch screen", "Tab"]),
-        ];
-        Table::new(rows, widths).render(area, buf);
+    fn scroll_up(&mut self) -> Option<ScreenEvent> {
+        *self.state.offset_mut() = self.state.offset().saturating_sub(1);
+
+        None
     }
 
-    fn render_table_send_workspace(&self, area: Rect, buf: &mut Buffer) {
-        Paragraph::new("Send to Workspace Mode".bold())
-            .alignment(Alignment::Left)
+    fn render_keybinds(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Keybinds".blue())
+            .alignment(Alignment::Center)
             .render(area, buf);
-        let area = area.offset(Offset { x: 0, y: 1 });
-
-        let widths = [Constraint::Max(20), Constraint::Max(10)];
-        let rows = [
-            Row::new(vec!["Choose workspace", "Enter"]),
-            Row::new(vec!["Choose workspace", "0-9"]),
-        ];
-        Table::new(rows, widths).render(area, buf);
+
+        let area = area.offset(Offset { x: 0, y: 1 }).intersection(area);
+        let widths = [Constraint::Max(22), Constraint::Max(14)];
+        let mut rows = Vec::new();
+        for (title, keybinds) in SECTIONS {
+            rows.push(Row::new(vec![title.bold()]));
+            for (action, key) in keybinds.iter() {
+                rows.push(Row::new(vec![*action, *key]));
+            }
+            rows.push(Row::new(vec![""]));
+        }
+        StatefulWidget::render(Table::new(rows, widths), area, buf, &mut self.state.clone());
     }
 }
 
@@ -88,6 +232,12 @@ impl WidgetRef for HelpScreen {
 impl ScreenWidget for HelpScreen {
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
         match key_event.code {
+            KeyCode::Down => self.scroll_down(),
+            KeyCode::Char('j') => self.scroll_down(),
+
+            KeyCode::Up => self.scroll_up(),
+            KeyCode::Char('k') => self.scroll_up(),
+
             KeyCode::Char('?') => Some(ScreenEvent::PopScreen),
             _ => None,
         }
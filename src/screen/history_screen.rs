@@ -0,0 +1,116 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Margin, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Row, StatefulWidget, Table, TableState, Widget, WidgetRef},
+};
+
+use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
+
+/// Lists the undo history, most recent first. Undoing an entry also undoes
+/// everything done after it.
+pub struct HistoryScreen {
+    state: TableState,
+    descriptions: Vec<String>,
+}
+
+impl HistoryScreen {
+    pub fn new(descriptions: Vec<String>) -> HistoryScreen {
+        HistoryScreen {
+            state: TableState::default().with_selected(Some(0)),
+            descriptions,
+        }
+    }
+
+    fn table_move_down(&mut self) -> Option<ScreenEvent> {
+        if let Some(i) = self.state.selected() {
+            if i + 1 < self.descriptions.len() {
+                self.state.select_next();
+            }
+        }
+
+        None
+    }
+
+    fn table_move_up(&mut self) -> Option<ScreenEvent> {
+        self.state.select_previous();
+
+        None
+    }
+
+    fn undo_to_selected(&mut self) -> Option<ScreenEvent> {
+        let index = self.state.selected()?;
+        if index < self.descriptions.len() {
+            return Some(ScreenEvent::PopAndUndo(index + 1));
+        }
+
+        None
+    }
+}
+
+impl WidgetRef for HistoryScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Undo ".into(),
+            "<Enter>".blue().bold(),
+            " Back ".into(),
+            "<ESC>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        Block::bordered()
+            .title_top(" History ")
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        if self.descriptions.is_empty() {
+            Paragraph::new("Nothing to undo")
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        }
+
+        let rows: Vec<Row> = self
+            .descriptions
+            .iter()
+            .map(|description| Row::new(vec![description.clone()]))
+            .collect();
+        let table = Table::new(rows, [Constraint::Min(30)])
+            .header(Row::new(vec!["Operation"]).bold())
+            .row_highlight_style(Style::new().reversed());
+        StatefulWidget::render(table, area, buf, &mut self.state.clone());
+    }
+}
+
+impl ScreenWidget for HistoryScreen {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        match key_event.code {
+            KeyCode::Down => self.table_move_down(),
+            KeyCode::Char('j') => self.table_move_down(),
+
+            KeyCode::Up => self.table_move_up(),
+            KeyCode::Char('k') => self.table_move_up(),
+
+            KeyCode::Enter => self.undo_to_selected(),
+
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    fn refresh(&mut self) {}
+}
+impl Screen for HistoryScreen {}
@@ -0,0 +1,230 @@
+use anyhow::{Context, Result};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, ModifierKeyCode, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Widget, WidgetRef},
+};
+
+use crate::{
+    app::{
+        client_table::ClientTable,
+        columns::{ClientColumn, Columns},
+        command_line::Selection,
+        mouse::{Clicks, SCROLL_ROWS},
+        scrolling::{Motion, MotionKeys},
+    },
+    hypr::{Client, Hypr},
+};
+
+use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
+
+/// Alt-tab style list of clients in most-recently-used order.
+pub struct MruScreen<'a> {
+    client_table: ClientTable<'a>,
+    columns: Columns<ClientColumn>,
+    peek: bool,
+    motion_keys: MotionKeys,
+    clicks: Clicks,
+    /// Whether letting go of a modifier held down to launch wmt confirms.
+    confirm_on_release: bool,
+    /// Modifiers pressed since the screen opened. Releasing them doesn't
+    /// confirm, so e.g. Ctrl-d can be used.
+    pressed_modifiers: KeyModifiers,
+}
+
+impl<'a> MruScreen<'_> {
+    /// The list is always in focus order, so `columns` are never sorted on.
+    /// `confirm_on_release` is for when wmt was launched with `--mru` from a
+    /// bind, see [`MruScreen::handle_key_release`].
+    pub fn new(columns: Vec<ClientColumn>, confirm_on_release: bool) -> Result<MruScreen<'a>> {
+        let hypr = Hypr::without_own_window().context("Connecting to Hyprland")?;
+        Ok(Self::with_hypr(hypr, columns, confirm_on_release))
+    }
+
+    /// Like [`MruScreen::new`], for when `hypr` was already loaded with
+    /// [`Hypr::without_own_window`].
+    pub fn with_hypr(
+        hypr: Hypr,
+        columns: Vec<ClientColumn>,
+        confirm_on_release: bool,
+    ) -> MruScreen<'a> {
+        let columns = Columns::new(columns, None);
+        MruScreen {
+            client_table: Self::recent_clients_table(hypr.clients, &columns),
+            columns,
+            peek: false,
+            motion_keys: MotionKeys::default(),
+            clicks: Clicks::default(),
+            confirm_on_release,
+            pressed_modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    /// The current window stays at the top while the cursor starts on the
+    /// previous one, so confirming straight away flips between the two.
+    fn recent_clients_table(
+        mut clients: Vec<Client>,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        clients.sort_by_key(|client| client.focus_id);
+        let active_address = clients.first().map(|client| client.address.clone());
+        let mut client_table = ClientTable::new(clients, active_address, columns);
+        client_table.select(1);
+        client_table
+    }
+
+    fn table_move(&mut self, motion: Motion) -> Option<ScreenEvent> {
+        self.client_table.move_cursor(motion);
+
+        self.peek_selected_client()
+    }
+
+    fn toggle_peek(&mut self) -> Option<ScreenEvent> {
+        self.peek = !self.peek;
+
+        self.peek_selected_client()
+    }
+
+    fn peek_selected_client(&self) -> Option<ScreenEvent> {
+        if self.peek {
+            return Some(ScreenEvent::Peek(self.client_table.selected_client()?));
+        }
+
+        None
+    }
+
+    fn focus_selected_client(&mut self) -> Option<ScreenEvent> {
+        let (workspace_option, client_address) = self.client_table.selected_workspace();
+        if let Some(workspace) = workspace_option {
+            if crate::hypr::switch_to_workspace(&workspace, client_address).is_ok() {
+                return Some(ScreenEvent::Close);
+            }
+        }
+
+        None
+    }
+}
+
+impl WidgetRef for MruScreen<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Back ".into(),
+            "<ESC>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        let mut block = Block::bordered()
+            .title_top(" Recent ")
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK);
+        if self.peek {
+            block = block.title_top(Line::from(" Peek ".green()).right_aligned());
+        }
+        block.render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        self.client_table.render(area, buf);
+    }
+}
+
+impl ScreenWidget for MruScreen<'_> {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        if let KeyCode::Modifier(modifier) = key_event.code {
+            if key_event.kind == KeyEventKind::Press {
+                self.pressed_modifiers |= modifier_flag(modifier);
+            }
+            return None;
+        }
+        if self.motion_keys.handle_count(key_event) {
+            return None;
+        }
+        if let Some(motion) = self.motion_keys.motion(key_event) {
+            return self.table_move(motion);
+        }
+
+        match key_event.code {
+            KeyCode::Tab => self.table_move(Motion::Down(1)),
+            KeyCode::BackTab => self.table_move(Motion::Up(1)),
+
+            KeyCode::Enter => self.focus_selected_client(),
+            KeyCode::Char(' ') => self.focus_selected_client(),
+            KeyCode::Char('p') => self.toggle_peek(),
+
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    /// Letting go of the modifier held down to launch or cycle through the
+    /// list confirms the selection, like a desktop alt-tab switcher. Its press
+    /// came before wmt started, so that's how it is told apart from modifiers
+    /// pressed since. Shift is left out so it can be used to cycle backwards.
+    fn handle_key_release(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        let KeyCode::Modifier(modifier) = key_event.code else {
+            return None;
+        };
+        let flag = modifier_flag(modifier);
+        if !self.confirm_on_release || flag.is_empty() || self.pressed_modifiers.contains(flag) {
+            return None;
+        }
+
+        self.focus_selected_client()
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Option<ScreenEvent> {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !self.client_table.click(column, row) {
+                    return None;
+                }
+                if self.clicks.is_double(column, row) {
+                    return self.focus_selected_client();
+                }
+                self.peek_selected_client()
+            }
+            MouseEventKind::ScrollDown => self.table_move(Motion::Down(SCROLL_ROWS)),
+            MouseEventKind::ScrollUp => self.table_move(Motion::Up(SCROLL_ROWS)),
+            _ => None,
+        }
+    }
+
+    fn selection(&self) -> Selection {
+        self.client_table.selection()
+    }
+
+    fn refresh(&mut self) {
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            self.client_table = Self::recent_clients_table(hypr.clients, &self.columns);
+        }
+    }
+}
+impl Screen for MruScreen<'_> {}
+
+/// The modifier flag `modifier` sets, empty for Shift and the ISO level
+/// shifts.
+fn modifier_flag(modifier: ModifierKeyCode) -> KeyModifiers {
+    match modifier {
+        ModifierKeyCode::LeftControl | ModifierKeyCode::RightControl => KeyModifiers::CONTROL,
+        ModifierKeyCode::LeftAlt | ModifierKeyCode::RightAlt => KeyModifiers::ALT,
+        ModifierKeyCode::LeftSuper | ModifierKeyCode::RightSuper => KeyModifiers::SUPER,
+        ModifierKeyCode::LeftHyper | ModifierKeyCode::RightHyper => KeyModifiers::HYPER,
+        ModifierKeyCode::LeftMeta | ModifierKeyCode::RightMeta => KeyModifiers::META,
+        _ => KeyModifiers::empty(),
+    }
+}
@@ -0,0 +1,143 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Margin, Rect},
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Row, Table, Widget, WidgetRef},
+};
+
+use crate::{
+    app::history::{Change, Operation},
+    config::Rule,
+    hypr::Hypr,
+    organize::{self, Move},
+};
+
+use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
+
+/// Dry run of the configured rules, listing the moves before running them.
+pub struct OrganizeScreen {
+    rules: Vec<Rule>,
+    /// The planned moves, or why they couldn't be planned.
+    plan: Result<Vec<Move>, String>,
+}
+
+impl OrganizeScreen {
+    pub fn new(rules: Vec<Rule>) -> OrganizeScreen {
+        let plan = Self::plan(&rules);
+        OrganizeScreen { rules, plan }
+    }
+
+    fn plan(rules: &[Rule]) -> Result<Vec<Move>, String> {
+        let hypr = Hypr::without_own_window().map_err(|err| format!("{:#}", err))?;
+        organize::plan(rules, &hypr.clients).map_err(|err| format!("{:#}", err))
+    }
+
+    fn apply(&mut self) -> Option<ScreenEvent> {
+        let Ok(moves) = &self.plan else {
+            return None;
+        };
+        if moves.is_empty() {
+            return None;
+        }
+        if let Err(err) = organize::apply(moves) {
+            return Some(ScreenEvent::Error(err.context("Moving clients")));
+        }
+
+        let changes = moves
+            .iter()
+            .map(|planned| Change::Moved {
+                address: planned.address.clone(),
+                from: planned.from.target(),
+            })
+            .collect();
+        let description = format!("Organize {} windows", moves.len());
+        Some(ScreenEvent::PopAndRecord(Operation::new(
+            description,
+            changes,
+        )))
+    }
+
+    fn render_moves(&self, moves: &[Move], area: Rect, buf: &mut Buffer) {
+        let widths = [
+            Constraint::Max(15),
+            Constraint::Min(30),
+            Constraint::Max(15),
+            Constraint::Max(15),
+        ];
+        let rows: Vec<Row> = moves
+            .iter()
+            .map(|planned| {
+                Row::new(vec![
+                    planned.class.clone(),
+                    planned.title.clone(),
+                    planned.from.name.clone(),
+                    planned.to.clone(),
+                ])
+            })
+            .collect();
+        Table::new(rows, widths)
+            .header(Row::new(vec!["Class", "Title", "From", "To"]).bold())
+            .render(area, buf);
+    }
+}
+
+impl WidgetRef for OrganizeScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Apply ".into(),
+            "<Enter>".blue().bold(),
+            " Back ".into(),
+            "<ESC>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        Block::bordered()
+            .title_top(" Organize ")
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        match &self.plan {
+            Ok(moves) if moves.is_empty() => {
+                let message = if self.rules.is_empty() {
+                    "No rules configured"
+                } else {
+                    "Every window is already where the rules want it"
+                };
+                Paragraph::new(message)
+                    .alignment(Alignment::Center)
+                    .render(area, buf);
+            }
+            Ok(moves) => self.render_moves(moves, area, buf),
+            Err(err) => Paragraph::new(err.clone().red()).render(area, buf),
+        }
+    }
+}
+
+impl ScreenWidget for OrganizeScreen {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        match key_event.code {
+            KeyCode::Enter => self.apply(),
+
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.plan = Self::plan(&self.rules);
+    }
+}
+impl Screen for OrganizeScreen {}
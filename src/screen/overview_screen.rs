@@ -0,0 +1,349 @@
+use std::cell::Cell;
+
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    app::{client_table::ACTIVE_MARKER, command_line::Selection},
+    hypr::{Client, Hypr},
+};
+
+use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
+
+/// Cards are never laid out narrower than this, fewer of them fit per row instead.
+const CARD_WIDTH: u16 = 24;
+const CARD_HEIGHT: u16 = 6;
+
+struct Card {
+    id: i32,
+    name: String,
+    windows: u32,
+    /// Each class on the workspace with how many of its windows are there.
+    classes: Vec<(String, usize)>,
+}
+
+struct MonitorGroup {
+    name: String,
+    cards: Vec<Card>,
+}
+
+/// Grid of workspace cards, one section per monitor. Special workspaces are
+/// left out, they have their own screen.
+pub struct OverviewScreen {
+    groups: Vec<MonitorGroup>,
+    active_workspace: i32,
+    /// Group and card index of the selected card.
+    selected: (usize, usize),
+    /// How many cards fit in a row, as of the last render. Moving up and down
+    /// depends on it.
+    columns: Cell<usize>,
+}
+
+impl OverviewScreen {
+    pub fn new() -> Result<OverviewScreen> {
+        let hypr = Hypr::without_own_window().context("Connecting to Hyprland")?;
+        let mut screen = OverviewScreen {
+            groups: Vec::new(),
+            active_workspace: hypr.active_workspace,
+            selected: (0, 0),
+            columns: Cell::new(1),
+        };
+        screen.groups = Self::build_groups(hypr);
+        screen.select_workspace(screen.active_workspace);
+        Ok(screen)
+    }
+
+    fn build_groups(hypr: Hypr) -> Vec<MonitorGroup> {
+        let mut groups = Vec::new();
+        for monitor in &hypr.monitors {
+            let mut workspaces: Vec<_> = hypr
+                .workspaces
+                .iter()
+                .filter(|workspace| workspace.id >= 0 && workspace.monitor_id == monitor.id)
+                .collect();
+            workspaces.sort_by_key(|workspace| workspace.id);
+
+            let cards: Vec<Card> = workspaces
+                .into_iter()
+                .map(|workspace| Card {
+                    id: workspace.id,
+                    name: workspace.name.clone(),
+                    windows: workspace.windows,
+                    classes: Self::count_classes(&hypr.clients, workspace.id),
+                })
+                .collect();
+            if !cards.is_empty() {
+                groups.push(MonitorGroup {
+                    name: monitor.name.clone(),
+                    cards,
+                });
+            }
+        }
+
+        groups
+    }
+
+    fn count_classes(clients: &[Client], workspace_id: i32) -> Vec<(String, usize)> {
+        let mut classes: Vec<(String, usize)> = Vec::new();
+        for client in clients.iter().filter(|c| c.workspace.id == workspace_id) {
+            match classes.iter_mut().find(|(class, _)| *class == client.class) {
+                Some((_, count)) => *count += 1,
+                None => classes.push((client.class.clone(), 1)),
+            }
+        }
+
+        classes
+    }
+
+    fn select_workspace(&mut self, id: i32) {
+        for (group_index, group) in self.groups.iter().enumerate() {
+            if let Some(index) = group.cards.iter().position(|card| card.id == id) {
+                self.selected = (group_index, index);
+                return;
+            }
+        }
+        self.selected = (0, 0);
+    }
+
+    fn selected_card(&self) -> Option<&Card> {
+        let (group, index) = self.selected;
+        self.groups.get(group)?.cards.get(index)
+    }
+
+    fn group_len(&self, group: usize) -> usize {
+        self.groups.get(group).map_or(0, |group| group.cards.len())
+    }
+
+    fn move_right(&mut self) -> Option<ScreenEvent> {
+        let (group, index) = self.selected;
+        if index + 1 < self.group_len(group) {
+            self.selected = (group, index + 1);
+        } else if group + 1 < self.groups.len() {
+            self.selected = (group + 1, 0);
+        }
+
+        None
+    }
+
+    fn move_left(&mut self) -> Option<ScreenEvent> {
+        let (group, index) = self.selected;
+        if index > 0 {
+            self.selected = (group, index - 1);
+        } else if group > 0 {
+            self.selected = (group - 1, self.group_len(group - 1) - 1);
+        }
+
+        None
+    }
+
+    /// Goes to the card below, the last card when the row below is shorter,
+    /// or the first row of the next monitor.
+    fn move_down(&mut self) -> Option<ScreenEvent> {
+        let columns = self.columns.get();
+        let (group, index) = self.selected;
+        let len = self.group_len(group);
+        if len == 0 {
+            return None;
+        }
+        if index + columns < len {
+            self.selected = (group, index + columns);
+        } else if index / columns < (len - 1) / columns {
+            self.selected = (group, len - 1);
+        } else if group + 1 < self.groups.len() {
+            let column = index % columns;
+            self.selected = (group + 1, column.min(self.group_len(group + 1) - 1));
+        }
+
+        None
+    }
+
+    fn move_up(&mut self) -> Option<ScreenEvent> {
+        let columns = self.columns.get();
+        let (group, index) = self.selected;
+        if index >= columns {
+            self.selected = (group, index - columns);
+        } else if group > 0 {
+            let len = self.group_len(group - 1);
+            let last_row = (len - 1) / columns * columns;
+            self.selected = (group - 1, (last_row + index).min(len - 1));
+        }
+
+        None
+    }
+
+    fn switch_to_selected_workspace(&mut self) -> Option<ScreenEvent> {
+        // Special workspaces aren't listed, so the id always works as a target
+        let id = self.selected_card()?.id;
+        if crate::hypr::switch_to_workspace(&id.to_string(), None).is_ok() {
+            return Some(ScreenEvent::Close);
+        }
+
+        None
+    }
+
+    fn render_card(&self, card: &Card, selected: bool, area: Rect, buf: &mut Buffer) {
+        let marker = if card.id == self.active_workspace {
+            format!("{} ", ACTIVE_MARKER)
+        } else {
+            String::new()
+        };
+        let mut block = Block::bordered()
+            .title(format!(" {}{} ", marker, card.name))
+            .title_top(Line::from(format!(" {} ", card.windows)).right_aligned());
+        if selected {
+            block = block
+                .border_set(border::THICK)
+                .border_style(Style::new().blue());
+        }
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let rows = inner.height as usize;
+        let mut lines: Vec<Line> = card
+            .classes
+            .iter()
+            .map(|(class, count)| match count {
+                1 => Line::from(class.clone()),
+                _ => Line::from(format!("{} ×{}", class, count)),
+            })
+            .collect();
+        if lines.len() > rows {
+            let hidden = lines.len() - rows + 1;
+            lines.truncate(rows - 1);
+            lines.push(Line::from(format!("+{} more", hidden).italic()));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from("Empty".italic()));
+        }
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    /// Lays the groups out top to bottom, scrolling just far enough for the
+    /// selected card to be on screen.
+    fn render_grid(&self, area: Rect, buf: &mut Buffer) {
+        if self.groups.is_empty() {
+            Paragraph::new("No workspaces".italic()).render(area, buf);
+            return;
+        }
+
+        let columns = (area.width / CARD_WIDTH).max(1);
+        self.columns.set(columns as usize);
+        let card_width = area.width / columns;
+
+        // Position of every header and card relative to the top of the grid
+        let mut headers = Vec::new();
+        let mut cards = Vec::new();
+        let mut y = 0;
+        for (group_index, group) in self.groups.iter().enumerate() {
+            headers.push((y, group));
+            y += 1;
+            for (index, card) in group.cards.iter().enumerate() {
+                let row = (index / columns as usize) as u16;
+                let column = (index % columns as usize) as u16;
+                let selected = self.selected == (group_index, index);
+                cards.push((y + row * CARD_HEIGHT, column * card_width, card, selected));
+            }
+            y += group.cards.len().div_ceil(columns as usize) as u16 * CARD_HEIGHT;
+        }
+
+        let scroll = cards
+            .iter()
+            .find(|(.., selected)| *selected)
+            .map_or(0, |(top, ..)| {
+                (top + CARD_HEIGHT).saturating_sub(area.height)
+            });
+        let fits = |top: u16, height: u16| top >= scroll && top + height <= scroll + area.height;
+
+        for (top, group) in headers {
+            if fits(top, 1) {
+                let header = Rect::new(area.x, area.y + top - scroll, area.width, 1);
+                Paragraph::new(group.name.clone().bold()).render(header, buf);
+            }
+        }
+        for (top, x, card, selected) in cards {
+            if fits(top, CARD_HEIGHT) {
+                let card_area =
+                    Rect::new(area.x + x, area.y + top - scroll, card_width, CARD_HEIGHT);
+                self.render_card(card, selected, card_area, buf);
+            }
+        }
+    }
+}
+
+impl WidgetRef for OverviewScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Switch ".into(),
+            "<Enter>".blue().bold(),
+            " Back ".into(),
+            "<ESC>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        Block::bordered()
+            .title_top(" Overview ")
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        self.render_grid(area, buf);
+    }
+}
+
+impl ScreenWidget for OverviewScreen {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        match key_event.code {
+            KeyCode::Left => self.move_left(),
+            KeyCode::Char('h') => self.move_left(),
+
+            KeyCode::Down => self.move_down(),
+            KeyCode::Char('j') => self.move_down(),
+
+            KeyCode::Up => self.move_up(),
+            KeyCode::Char('k') => self.move_up(),
+
+            KeyCode::Right => self.move_right(),
+            KeyCode::Char('l') => self.move_right(),
+
+            KeyCode::Enter => self.switch_to_selected_workspace(),
+
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    /// Special workspaces aren't listed, so the id always works as a target.
+    fn selection(&self) -> Selection {
+        Selection {
+            workspace: self.selected_card().map(|card| card.id.to_string()),
+            ..Selection::default()
+        }
+    }
+
+    fn refresh(&mut self) {
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            let selected_id = self.selected_card().map(|card| card.id);
+            self.active_workspace = hypr.active_workspace;
+            self.groups = Self::build_groups(hypr);
+            self.select_workspace(selected_id.unwrap_or(self.active_workspace));
+        }
+    }
+}
+impl Screen for OverviewScreen {}
@@ -0,0 +1,324 @@
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Row, StatefulWidget, Table, TableState, Widget, WidgetRef},
+};
+
+use crate::{
+    app::{
+        history::{Change, Operation},
+        text_input::TextInput,
+    },
+    hypr::{self, Client, Hypr},
+};
+
+use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
+
+/// A special workspace, named without its `special:` prefix, and its clients.
+struct Scratchpad {
+    name: String,
+    clients: Vec<Client>,
+}
+
+/// A row of the table, either a scratchpad or one of its clients.
+enum Entry {
+    Scratchpad(usize),
+    Client(usize, usize),
+}
+
+/// The client being sent into a scratchpad.
+struct Sending {
+    address: String,
+    class: String,
+    from: String,
+}
+
+/// Lists the special workspaces and their clients. Opened with a client, it
+/// picks the scratchpad to send that client into instead.
+pub struct ScratchpadScreen {
+    state: TableState,
+    scratchpads: Vec<Scratchpad>,
+    entries: Vec<Entry>,
+    active_workspace: i32,
+    sending: Option<Sending>,
+    /// The name of a new scratchpad being typed, while sending.
+    new_name: Option<TextInput>,
+}
+
+impl ScratchpadScreen {
+    pub fn new() -> Result<ScratchpadScreen> {
+        let hypr = Hypr::without_own_window().context("Connecting to Hyprland")?;
+        let mut screen = ScratchpadScreen {
+            state: TableState::default().with_selected(Some(0)),
+            scratchpads: Vec::new(),
+            entries: Vec::new(),
+            active_workspace: hypr.active_workspace,
+            sending: None,
+            new_name: None,
+        };
+        screen.load(hypr);
+
+        Ok(screen)
+    }
+
+    pub fn sending(client: &Client) -> Result<ScratchpadScreen> {
+        let mut screen = Self::new()?;
+        screen.sending = Some(Sending {
+            address: client.address.clone(),
+            class: client.class.clone(),
+            from: client.workspace.target(),
+        });
+
+        Ok(screen)
+    }
+
+    fn load(&mut self, hypr: Hypr) {
+        self.active_workspace = hypr.active_workspace;
+        self.scratchpads.clear();
+        for client in hypr.clients {
+            // Named workspaces have negative ids too, so go by the prefix
+            let Some(name) = client.workspace.name.strip_prefix("special:") else {
+                continue;
+            };
+            let name = name.to_string();
+            match self.scratchpads.iter_mut().find(|s| s.name == name) {
+                Some(scratchpad) => scratchpad.clients.push(client),
+                None => self.scratchpads.push(Scratchpad {
+                    name,
+                    clients: vec![client],
+                }),
+            }
+        }
+        self.scratchpads.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.entries.clear();
+        for (i, scratchpad) in self.scratchpads.iter().enumerate() {
+            self.entries.push(Entry::Scratchpad(i));
+            for j in 0..scratchpad.clients.len() {
+                self.entries.push(Entry::Client(i, j));
+            }
+        }
+        if self
+            .state
+            .selected()
+            .is_some_and(|i| i >= self.entries.len())
+        {
+            self.state.select(Some(0));
+        }
+    }
+
+    fn table_move_down(&mut self) -> Option<ScreenEvent> {
+        if let Some(i) = self.state.selected() {
+            if i + 1 < self.entries.len() {
+                self.state.select_next();
+            }
+        }
+
+        None
+    }
+
+    fn table_move_up(&mut self) -> Option<ScreenEvent> {
+        self.state.select_previous();
+
+        None
+    }
+
+    fn selected_scratchpad(&self) -> Option<&Scratchpad> {
+        let index = match self.entries.get(self.state.selected()?)? {
+            Entry::Scratchpad(i) => *i,
+            Entry::Client(i, _) => *i,
+        };
+        self.scratchpads.get(index)
+    }
+
+    fn selected_client(&self) -> Option<&Client> {
+        match self.entries.get(self.state.selected()?)? {
+            Entry::Scratchpad(_) => None,
+            Entry::Client(i, j) => self.scratchpads.get(*i)?.clients.get(*j),
+        }
+    }
+
+    fn toggle_selected(&mut self) -> Option<ScreenEvent> {
+        let scratchpad = self.selected_scratchpad()?;
+        if hypr::toggle_special_workspace(&scratchpad.name).is_ok() {
+            return Some(ScreenEvent::Close);
+        }
+
+        None
+    }
+
+    /// Moves the selected client out of its scratchpad onto the workspace
+    /// that is on screen.
+    fn pull_selected(&mut self) -> Option<ScreenEvent> {
+        let client = self.selected_client()?;
+        if hypr::send_to_workspace(&self.active_workspace.to_string(), client.address.clone())
+            .is_err()
+        {
+            return None;
+        }
+
+        Some(ScreenEvent::Record(Operation::new(
+            format!("Pull {} out of {}", client.class, client.workspace.name),
+            vec![Change::Moved {
+                address: client.address.clone(),
+                from: client.workspace.target(),
+            }],
+        )))
+    }
+
+    fn send_to_scratchpad(&self, name: &str) -> Option<ScreenEvent> {
+        let sending = self.sending.as_ref()?;
+        if name.is_empty() {
+            return None;
+        }
+        let target = format!("special:{}", name);
+        if hypr::send_many_to_workspaces(&[(target, sending.address.clone())]).is_err() {
+            return None;
+        }
+
+        Some(ScreenEvent::PopAndRecord(Operation::new(
+            format!("Send {} to scratchpad {}", sending.class, name),
+            vec![Change::Moved {
+                address: sending.address.clone(),
+                from: sending.from.clone(),
+            }],
+        )))
+    }
+
+    fn send_to_selected(&mut self) -> Option<ScreenEvent> {
+        let name = self.selected_scratchpad()?.name.clone();
+        self.send_to_scratchpad(&name)
+    }
+
+    fn handle_new_name_key(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        let new_name = self.new_name.as_mut()?;
+        if new_name.handle_key_event(key_event) {
+            return None;
+        }
+        match key_event.code {
+            KeyCode::Enter => {
+                let name = new_name.value().to_string();
+                self.send_to_scratchpad(&name)
+            }
+            KeyCode::Esc => {
+                self.new_name = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render_scratchpads(&self, area: Rect, buf: &mut Buffer) {
+        if self.entries.is_empty() {
+            Paragraph::new("No scratchpads")
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        }
+
+        let widths = [Constraint::Max(20), Constraint::Min(30)];
+        let rows: Vec<Row> = self
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Scratchpad(i) => {
+                    let scratchpad = &self.scratchpads[*i];
+                    Row::new(vec![
+                        scratchpad.name.clone(),
+                        format!("{} windows", scratchpad.clients.len()),
+                    ])
+                    .bold()
+                }
+                Entry::Client(i, j) => {
+                    let client = &self.scratchpads[*i].clients[*j];
+                    Row::new(vec![format!("  {}", client.class), client.title.clone()])
+                }
+            })
+            .collect();
+        let table = Table::new(rows, widths).row_highlight_style(Style::new().reversed());
+        StatefulWidget::render(table, area, buf, &mut self.state.clone());
+    }
+}
+
+impl WidgetRef for ScratchpadScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Back ".into(),
+            "<ESC>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        let title = match &self.sending {
+            Some(sending) => format!(" Send {} to Scratchpad ", sending.class),
+            None => " Scratchpads ".to_string(),
+        };
+        Block::bordered()
+            .title_top(title)
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        match &self.new_name {
+            Some(new_name) => {
+                let [table_area, input_area] =
+                    Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+                self.render_scratchpads(table_area, buf);
+                new_name.render_ref(input_area, buf);
+            }
+            None => self.render_scratchpads(area, buf),
+        }
+    }
+}
+
+impl ScreenWidget for ScratchpadScreen {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        if self.new_name.is_some() {
+            return self.handle_new_name_key(key_event);
+        }
+
+        match key_event.code {
+            KeyCode::Down => self.table_move_down(),
+            KeyCode::Char('j') => self.table_move_down(),
+
+            KeyCode::Up => self.table_move_up(),
+            KeyCode::Char('k') => self.table_move_up(),
+
+            KeyCode::Enter if self.sending.is_some() => self.send_to_selected(),
+            KeyCode::Char('n') if self.sending.is_some() => {
+                self.new_name = Some(TextInput::default());
+                None
+            }
+            KeyCode::Enter => self.toggle_selected(),
+            KeyCode::Char('t') => self.toggle_selected(),
+            KeyCode::Char('p') => self.pull_selected(),
+
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    fn refresh(&mut self) {
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            self.load(hypr);
+        }
+    }
+
+    fn captures_input(&self) -> bool {
+        self.new_name.is_some()
+    }
+}
+impl Screen for ScratchpadScreen {}
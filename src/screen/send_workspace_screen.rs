@@ -1,62 +1,144 @@
 use anyhow::Context;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Margin, Rect},
+    layout::{Constraint, Layout, Margin, Rect},
     style::Stylize,
     symbols::border,
     text::Line,
     widgets::{Block, Widget, WidgetRef},
 };
 
-use crate::{app::workspace_table::WorkspaceTable, hypr::Hypr};
+use crate::{
+    app::{
+        columns::{Columns, WorkspaceColumn},
+        command_line::Selection,
+        history::{Change, Operation},
+        mouse::{Clicks, SCROLL_ROWS},
+        scrolling::{Motion, MotionKeys},
+        text_input::TextInput,
+        workspace_table::WorkspaceTable,
+    },
+    hypr::{self, Client, Hypr},
+};
 
 use super::{help_screen::HelpScreen, Screen, ScreenEvent, ScreenWidget};
 
+/// Hyprland's target for the first workspace without any windows.
+const NEW_WORKSPACE: &str = "empty";
+
 pub struct SendWorkspaceScreen<'a> {
     workspace_table: WorkspaceTable<'a>,
+    columns: Columns<WorkspaceColumn>,
     client_address: String,
+    client_class: String,
+    /// Where the client was before being sent, for undo.
+    client_workspace: String,
+    /// The name of a new workspace being typed.
+    new_name: Option<TextInput>,
+    motion_keys: MotionKeys,
+    clicks: Clicks,
 }
 
 impl<'a> SendWorkspaceScreen<'_> {
-    pub fn new(client_address: String) -> SendWorkspaceScreen<'a> {
-        let hypr = Hypr::new().context("Connecting to Hyprland").unwrap();
-        let workspace_table = WorkspaceTable::new(hypr.workspaces);
+    pub fn new(client: &Client, columns: Columns<WorkspaceColumn>) -> SendWorkspaceScreen<'a> {
+        let hypr = Hypr::without_own_window()
+            .context("Connecting to Hyprland")
+            .unwrap();
+        let workspace_table = WorkspaceTable::with_new_workspace_row(
+            hypr.workspaces,
+            Some(hypr.active_workspace),
+            &columns,
+        );
         SendWorkspaceScreen {
             workspace_table,
-            client_address,
+            columns,
+            client_address: client.address.clone(),
+            client_class: client.class.clone(),
+            client_workspace: client.workspace.target(),
+            new_name: None,
+            motion_keys: MotionKeys::default(),
+            clicks: Clicks::default(),
         }
     }
 
-    fn table_move_down(&mut self) -> Option<ScreenEvent> {
-        self.workspace_table.move_down();
+    fn table_move(&mut self, motion: Motion) -> Option<ScreenEvent> {
+        self.workspace_table.move_cursor(motion);
 
         None
     }
 
-    fn table_move_up(&mut self) -> Option<ScreenEvent> {
-        self.workspace_table.move_up();
+    fn send_to_selected_workspace(&mut self) -> Option<ScreenEvent> {
+        if self.workspace_table.new_workspace_selected() {
+            self.new_name = Some(TextInput::default());
+            return None;
+        }
+        let workspace = self.workspace_table.selected()?.target();
+        self.send_to_workspace(&workspace)
+    }
+
+    /// `workspace` is anything `movetoworkspacesilent` accepts.
+    fn send_to_workspace(&mut self, workspace: &str) -> Option<ScreenEvent> {
+        let moves = [(workspace.to_string(), self.client_address.clone())];
+        if hypr::send_many_to_workspaces(&moves).is_ok() {
+            return Some(ScreenEvent::PopAndRecord(self.operation(workspace)));
+        }
 
         None
     }
 
-    fn send_to_selected_workspace(&mut self) -> Option<ScreenEvent> {
-        let id_option = self.workspace_table.selected_workspace();
-        if let Some(id) = id_option {
-            if let Ok(_) = crate::hypr::send_to_workspace(id, self.client_address.clone()) {
-                return Some(ScreenEvent::PopAndRefresh);
-            }
+    fn follow_to_selected_workspace(&mut self) -> Option<ScreenEvent> {
+        if self.workspace_table.new_workspace_selected() {
+            return self.follow_to_workspace(NEW_WORKSPACE);
+        }
+        let workspace = self.workspace_table.selected()?.target();
+        self.follow_to_workspace(&workspace)
+    }
+
+    /// Sends the client and switches to it, closing wmt like switching does.
+    fn follow_to_workspace(&mut self, workspace: &str) -> Option<ScreenEvent> {
+        if hypr::follow_to_workspace(workspace, &self.client_address).is_ok() {
+            return Some(ScreenEvent::Close);
         }
 
         None
     }
 
-    fn send_to_workspace(&mut self, id: u32) -> Option<ScreenEvent> {
-        if let Ok(_) = crate::hypr::send_to_workspace(id, self.client_address.clone()) {
-            return Some(ScreenEvent::PopAndRefresh);
+    /// Sends the client to a workspace named after the typed text, or to the
+    /// first free one when nothing was typed.
+    fn handle_new_name_key(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        let new_name = self.new_name.as_mut()?;
+        if new_name.handle_key_event(key_event) {
+            return None;
+        }
+        match key_event.code {
+            KeyCode::Enter => {
+                let workspace = match new_name.value().trim() {
+                    "" => NEW_WORKSPACE.to_string(),
+                    name => format!("name:{}", name),
+                };
+                self.send_to_workspace(&workspace)
+            }
+            KeyCode::Esc => {
+                self.new_name = None;
+                None
+            }
+            _ => None,
         }
+    }
 
-        None
+    fn operation(&self, workspace: &str) -> Operation {
+        let workspace = match workspace {
+            NEW_WORKSPACE => "a new workspace".to_string(),
+            workspace => format!("workspace {}", workspace),
+        };
+        Operation::new(
+            format!("Send {} to {}", self.client_class, workspace),
+            vec![Change::Moved {
+                address: self.client_address.clone(),
+                from: self.client_workspace.clone(),
+            }],
+        )
     }
 }
 
@@ -81,42 +163,93 @@ impl WidgetRef for SendWorkspaceScreen<'_> {
             vertical: 1,
         });
 
-        self.workspace_table.render(area, buf);
+        match &self.new_name {
+            Some(new_name) => {
+                let [table_area, input_area] =
+                    Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+                self.workspace_table.render(table_area, buf);
+                new_name.render_ref(input_area, buf);
+            }
+            None => self.workspace_table.render(area, buf),
+        }
     }
 }
 
 impl ScreenWidget for SendWorkspaceScreen<'_> {
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
-        match key_event.code {
-            KeyCode::Down => self.table_move_down(),
-            KeyCode::Char('j') => self.table_move_down(),
+        if self.new_name.is_some() {
+            return self.handle_new_name_key(key_event);
+        }
+
+        if key_event.modifiers.contains(KeyModifiers::ALT) {
+            return match key_event.code {
+                KeyCode::Enter => self.follow_to_selected_workspace(),
+                KeyCode::Char(digit @ '0'..='9') => self.follow_to_workspace(&digit.to_string()),
+                _ => None,
+            };
+        }
 
-            KeyCode::Up => self.table_move_up(),
-            KeyCode::Char('k') => self.table_move_up(),
+        if let Some(digit) = self.motion_keys.workspace_digit(key_event) {
+            return self.send_to_workspace(&digit.to_string());
+        }
+        if self.motion_keys.handle_count(key_event) {
+            return None;
+        }
+        if let Some(motion) = self.motion_keys.motion(key_event) {
+            return self.table_move(motion);
+        }
 
+        match key_event.code {
             KeyCode::Esc => Some(ScreenEvent::PopScreen),
             KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
 
             KeyCode::Enter => self.send_to_selected_workspace(),
-            KeyCode::Char('0') => self.send_to_workspace(0),
-            KeyCode::Char('1') => self.send_to_workspace(1),
-            KeyCode::Char('2') => self.send_to_workspace(2),
-            KeyCode::Char('3') => self.send_to_workspace(3),
-            KeyCode::Char('4') => self.send_to_workspace(4),
-            KeyCode::Char('5') => self.send_to_workspace(5),
-            KeyCode::Char('6') => self.send_to_workspace(6),
-            KeyCode::Char('7') => self.send_to_workspace(7),
-            KeyCode::Char('8') => self.send_to_workspace(8),
-            KeyCode::Char('9') => self.send_to_workspace(9),
+            KeyCode::Char('f') => self.follow_to_selected_workspace(),
+
+            _ => None,
+        }
+    }
 
+    /// The mouse is ignored while a new workspace name is being typed.
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Option<ScreenEvent> {
+        if self.new_name.is_some() {
+            return None;
+        }
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.workspace_table.click(column, row) && self.clicks.is_double(column, row) {
+                    return self.send_to_selected_workspace();
+                }
+                None
+            }
+            MouseEventKind::ScrollDown => self.table_move(Motion::Down(SCROLL_ROWS)),
+            MouseEventKind::ScrollUp => self.table_move(Motion::Up(SCROLL_ROWS)),
             _ => None,
         }
     }
 
+    /// The client being sent, with the workspace under the cursor.
+    fn selection(&self) -> Selection {
+        Selection {
+            address: Some(self.client_address.clone()),
+            workspace: self.workspace_table.selected().map(|ws| ws.target()),
+            class: Some(self.client_class.clone()),
+        }
+    }
+
     fn refresh(&mut self) {
-        if let Ok(hypr) = Hypr::new().context("Connecting to Hyprland") {
-            self.workspace_table = WorkspaceTable::new(hypr.workspaces);
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            self.workspace_table = WorkspaceTable::with_new_workspace_row(
+                hypr.workspaces,
+                Some(hypr.active_workspace),
+                &self.columns,
+            );
         }
     }
+
+    fn captures_input(&self) -> bool {
+        self.new_name.is_some()
+    }
 }
 impl Screen for SendWorkspaceScreen<'_> {}
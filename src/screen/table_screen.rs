@@ -1,92 +1,569 @@
+use std::{cell::Cell, rc::Rc};
+
 use anyhow::{Context, Result};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
-    layout::{Margin, Rect},
-    style::Stylize,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
     symbols::border,
     text::{Line, Span},
     widgets::{Block, Widget, WidgetRef},
 };
 
 use crate::{
-    app::{client_table::ClientTable, workspace_table::WorkspaceTable},
-    hypr::Hypr,
+    app::{
+        client_table::{ClientGroup, ClientTable},
+        columns::{ClientColumn, Columns, WorkspaceColumn},
+        command_line::Selection,
+        history::{Change, Operation},
+        minimap::Minimap,
+        mouse::{Clicks, SCROLL_ROWS},
+        scrolling::{Motion, MotionKeys},
+        workspace_table::WorkspaceTable,
+    },
+    config::{Config, Preselect},
+    hypr::{self, Client, Hypr, Monitor},
+    marks, minimize,
+    state::State,
 };
 
 use super::{
-    help_screen::HelpScreen, send_workspace_screen::SendWorkspaceScreen, Screen, ScreenEvent,
-    ScreenWidget,
+    console_screen::ConsoleScreen, help_screen::HelpScreen, mru_screen::MruScreen,
+    organize_screen::OrganizeScreen, overview_screen::OverviewScreen,
+    scratchpad_screen::ScratchpadScreen, send_workspace_screen::SendWorkspaceScreen,
+    workspace_screen::WorkspaceScreen, Screen, ScreenEvent, ScreenWidget,
 };
 
+/// Narrower than this, the workspace tab leaves out the minimap.
+const MINIMAP_MIN_WIDTH: u16 = 80;
+/// From this width on, the client and workspace tables are shown next to
+/// each other instead of one at a time.
+const SIDE_BY_SIDE_MIN_WIDTH: u16 = 120;
+
+/// What the client table groups its rows by.
+#[derive(Clone, Copy, PartialEq)]
+enum GroupBy {
+    None,
+    Workspace,
+    Monitor,
+    Class,
+}
+
+impl GroupBy {
+    fn next(self) -> GroupBy {
+        match self {
+            GroupBy::None => GroupBy::Workspace,
+            GroupBy::Workspace => GroupBy::Monitor,
+            GroupBy::Monitor => GroupBy::Class,
+            GroupBy::Class => GroupBy::None,
+        }
+    }
+}
+
 enum SelectedTable {
     Clients,
     Workspaces,
+    Minimized,
 }
 
 pub struct TableScreen<'a> {
     client_table: ClientTable<'a>,
     workspace_table: WorkspaceTable<'a>,
+    /// Minimized clients, listed with the workspace they will be restored to.
+    minimized_table: ClientTable<'a>,
+    active_workspace: i32,
+    own_address: Option<String>,
+    /// Used to lay out the minimap of the selected workspace.
+    monitors: Vec<Monitor>,
+    /// Every client, the client table may only show some of them.
+    clients: Vec<Client>,
+    active_client: Option<String>,
+    /// Whether the client table only lists the clients on the workspace
+    /// under the cursor in the workspace table.
+    filter_clients: bool,
+    group_by: GroupBy,
+    client_columns: Columns<ClientColumn>,
+    workspace_columns: Columns<WorkspaceColumn>,
     current_table: SelectedTable,
+    peek: bool,
+    config: Rc<Config>,
+    /// A key that needs another after it, `m` to set a mark, `'` to jump to
+    /// one or `i` to pick the group to move into.
+    pending_prefix: Option<char>,
+    motion_keys: MotionKeys,
+    clicks: Clicks,
+    /// Where the screen was last drawn, to find the tab titles under the mouse.
+    area: Cell<Rect>,
+    /// Whether the last render had the client and workspace tables side by side.
+    side_by_side: Cell<bool>,
 }
 
 impl<'a> TableScreen<'_> {
-    pub fn new() -> Result<TableScreen<'a>> {
-        let hypr = Hypr::new().context("Connecting to Hyprland")?;
-        let client_table = ClientTable::new(hypr.clients);
-        let workspace_table = WorkspaceTable::new(hypr.workspaces);
-        Ok(TableScreen {
+    /// `hypr` should come from [`Hypr::without_own_window`].
+    pub fn new(config: Rc<Config>, hypr: Hypr) -> TableScreen<'a> {
+        let active_workspace = hypr.active_workspace;
+        let own_address = hypr.own_address.clone();
+        let monitors = hypr.monitors.clone();
+        let clients = hypr.clients.clone();
+        let active_client = hypr.active_client();
+        let state = State::load().unwrap_or_default();
+        let client_columns = Columns::new(config.client_columns.clone(), state.client_sort);
+        let workspace_columns =
+            Columns::new(config.workspace_columns.clone(), state.workspace_sort);
+        let minimized_table = Self::build_minimized_table(&hypr, &client_columns);
+        let (client_table, workspace_table) =
+            Self::build_tables(hypr, config.preselect, &client_columns, &workspace_columns);
+        TableScreen {
             client_table,
             workspace_table,
+            minimized_table,
+            active_workspace,
+            own_address,
+            monitors,
+            clients,
+            active_client,
+            filter_clients: false,
+            group_by: GroupBy::None,
+            client_columns,
+            workspace_columns,
             current_table: SelectedTable::Clients,
-        })
+            peek: false,
+            config,
+            pending_prefix: None,
+            motion_keys: MotionKeys::default(),
+            clicks: Clicks::default(),
+            area: Cell::new(Rect::default()),
+            side_by_side: Cell::new(false),
+        }
+    }
+
+    /// Marks the active workspace and window, starting the cursor on them or
+    /// on the ones before them depending on `preselect`.
+    fn build_tables(
+        hypr: Hypr,
+        preselect: Preselect,
+        client_columns: &Columns<ClientColumn>,
+        workspace_columns: &Columns<WorkspaceColumn>,
+    ) -> (ClientTable<'a>, WorkspaceTable<'a>) {
+        let active_client = hypr.active_client();
+        let (selected_client, selected_workspace) = match preselect {
+            Preselect::Active => (active_client.clone(), Some(hypr.active_workspace)),
+            Preselect::Previous => (hypr.previous_client(), hypr.previous_workspace()),
+        };
+
+        let mut client_table = ClientTable::nested(hypr.clients, active_client, client_columns);
+        if let Some(client_address) = selected_client {
+            client_table.select_client(&client_address);
+        }
+        let mut workspace_table = WorkspaceTable::new(
+            hypr.workspaces,
+            Some(hypr.active_workspace),
+            workspace_columns,
+        );
+        if let Some(id) = selected_workspace {
+            workspace_table.select_workspace(id);
+        }
+
+        (client_table, workspace_table)
+    }
+
+    /// Rebuilds the client table from `clients`, keeping the cursor on the
+    /// same client if it is still listed.
+    fn rebuild_client_table(&mut self) {
+        let selected = self.client_table.selected_client();
+        let workspace = self.workspace_table.selected_workspace();
+        let clients = self
+            .clients
+            .iter()
+            .filter(|client| !self.filter_clients || Some(client.workspace.id) == workspace)
+            .cloned()
+            .collect();
+        let active_client = self.active_client.clone();
+        self.client_table = match self.group_by {
+            GroupBy::None => ClientTable::nested(clients, active_client, &self.client_columns),
+            _ => ClientTable::grouped(
+                self.group_clients(clients),
+                active_client,
+                &self.client_columns,
+            ),
+        };
+        if let Some(client_address) = selected {
+            self.client_table.select_client(&client_address);
+        }
+    }
+
+    /// Splits `clients` up as `group_by` says, with groups in workspace or
+    /// monitor order, or by class name.
+    fn group_clients(&self, clients: Vec<Client>) -> Vec<ClientGroup> {
+        let mut groups: Vec<(i32, ClientGroup)> = Vec::new();
+        for client in clients {
+            let (order, title) = match self.group_by {
+                GroupBy::Workspace => (client.workspace.id, client.workspace.name.clone()),
+                GroupBy::Monitor => match self.client_monitor(&client) {
+                    Some(monitor) => (monitor.id, monitor.name.clone()),
+                    None => (i32::MAX, "Unknown".to_string()),
+                },
+                GroupBy::Class | GroupBy::None => (0, client.class.clone()),
+            };
+            match groups.iter_mut().find(|(_, group)| group.title == title) {
+                Some((_, group)) => group.clients.push(client),
+                None => groups.push((
+                    order,
+                    ClientGroup {
+                        title,
+                        clients: vec![client],
+                    },
+                )),
+            }
+        }
+        groups.sort_by(|(a_order, a), (b_order, b)| {
+            a_order
+                .cmp(b_order)
+                .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+        });
+
+        groups.into_iter().map(|(_, group)| group).collect()
+    }
+
+    fn client_monitor(&self, client: &Client) -> Option<&Monitor> {
+        let workspace = self
+            .workspace_table
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.id == client.workspace.id)?;
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.id == workspace.monitor_id)
+    }
+
+    fn cycle_group_by(&mut self) -> Option<ScreenEvent> {
+        self.group_by = self.group_by.next();
+        self.rebuild_client_table();
+
+        None
+    }
+
+    /// Peeks at the window after the active one in the group under the
+    /// cursor and makes it the active one, so repeating this goes round the
+    /// group. wmt stays open until Space picks the window.
+    fn focus_next_in_group(&mut self) -> Option<ScreenEvent> {
+        let addresses = self.client_table.selected_group()?;
+        let active = addresses
+            .iter()
+            .position(|address| Some(address) == self.active_client.as_ref());
+        let next = match active {
+            Some(index) => &addresses[(index + 1) % addresses.len()],
+            None => addresses.first()?,
+        }
+        .clone();
+
+        // Peeking hands focus back to wmt's own window, so without one focus
+        // the window for real instead
+        if self.own_address.is_none() {
+            return match hypr::focus_client(&next) {
+                Ok(()) => Some(ScreenEvent::Close),
+                Err(err) => Some(ScreenEvent::Error(err)),
+            };
+        }
+
+        // Rebuild to move the active marker, staying on the header
+        self.active_client = Some(next.clone());
+        let row = self.client_table.selected_row();
+        self.rebuild_client_table();
+        if let Some(row) = row {
+            self.client_table.select(row);
+        }
+
+        Some(ScreenEvent::Peek(next))
+    }
+
+    /// Focuses the window Enter last went to in the group under the cursor.
+    fn focus_active_in_group(&self) -> Option<ScreenEvent> {
+        if !matches!(self.current_table, SelectedTable::Clients) {
+            return None;
+        }
+        let addresses = self.client_table.selected_group()?;
+        let active = self
+            .active_client
+            .as_ref()
+            .filter(|address| addresses.contains(address))?;
+        if hypr::focus_client(active).is_ok() {
+            return Some(ScreenEvent::Close);
+        }
+
+        None
+    }
+
+    /// Rebuilds the workspace table in the current sort order, keeping the
+    /// cursor on the same workspace.
+    fn rebuild_workspace_table(&mut self) {
+        let selected = self.workspace_table.selected_workspace();
+        let workspaces = std::mem::take(&mut self.workspace_table.workspaces);
+        self.workspace_table = WorkspaceTable::new(
+            workspaces,
+            Some(self.active_workspace),
+            &self.workspace_columns,
+        );
+        if let Some(id) = selected {
+            self.workspace_table.select_workspace(id);
+        }
+    }
+
+    /// Sorts the table on screen by the next column or direction, and
+    /// remembers it for the next time wmt opens.
+    fn cycle_sort(&mut self) -> Option<ScreenEvent> {
+        match self.current_table {
+            SelectedTable::Clients => {
+                self.client_columns.cycle_sort();
+                self.rebuild_client_table();
+            }
+            SelectedTable::Workspaces => {
+                self.workspace_columns.cycle_sort();
+                self.rebuild_workspace_table();
+            }
+            SelectedTable::Minimized => return None,
+        };
+        if let Err(err) = self.save_sort() {
+            return Some(ScreenEvent::Error(err.context("Saving the sort")));
+        }
+
+        None
+    }
+
+    fn save_sort(&self) -> Result<()> {
+        let mut state = State::load()?;
+        state.client_sort = self.client_columns.sort;
+        state.workspace_sort = self.workspace_columns.sort;
+        state.save()
+    }
+
+    fn toggle_client_filter(&mut self) -> Option<ScreenEvent> {
+        self.filter_clients = !self.filter_clients;
+        self.rebuild_client_table();
+
+        None
+    }
+
+    /// Kept in the order the clients were minimized, most recent first.
+    fn build_minimized_table(hypr: &Hypr, columns: &Columns<ClientColumn>) -> ClientTable<'a> {
+        let minimized = minimize::minimized_clients(hypr).unwrap_or_default();
+        let columns = Columns::new(columns.shown.clone(), None);
+        ClientTable::new(minimized, None, &columns)
     }
 
     fn next_border_screen(&mut self) -> Option<ScreenEvent> {
         match self.current_table {
             SelectedTable::Clients => self.current_table = SelectedTable::Workspaces,
+            // Side by side, Tab only moves between the two panes
+            SelectedTable::Workspaces if self.side_by_side.get() => {
+                self.current_table = SelectedTable::Clients
+            }
+            SelectedTable::Workspaces => self.current_table = SelectedTable::Minimized,
+            SelectedTable::Minimized => self.current_table = SelectedTable::Clients,
+        };
+
+        None
+    }
+
+    fn previous_border_screen(&mut self) -> Option<ScreenEvent> {
+        match self.current_table {
+            // Side by side, Shift+Tab only moves between the two panes
+            SelectedTable::Clients if self.side_by_side.get() => {
+                self.current_table = SelectedTable::Workspaces
+            }
+            SelectedTable::Clients => self.current_table = SelectedTable::Minimized,
             SelectedTable::Workspaces => self.current_table = SelectedTable::Clients,
+            SelectedTable::Minimized => self.current_table = SelectedTable::Workspaces,
         };
 
         None
     }
 
+    /// Shows the workspace table with a minimap of the selected workspace
+    /// beside it, when the terminal is wide enough for both.
+    fn render_workspaces(&self, area: Rect, buf: &mut Buffer) {
+        if area.width < MINIMAP_MIN_WIDTH {
+            self.workspace_table.render(area, buf);
+            return;
+        }
+
+        let [table_area, minimap_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        self.workspace_table.render(table_area, buf);
+        self.render_minimap(minimap_area, buf);
+    }
+
+    /// Draws the selected workspace's windows where they sit on its monitor.
+    fn render_minimap(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title(" Preview ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(id) = self.workspace_table.selected_workspace() else {
+            return;
+        };
+        let Some(monitor) = self
+            .workspace_table
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.id == id)
+            .and_then(|workspace| {
+                self.monitors
+                    .iter()
+                    .find(|monitor| monitor.id == workspace.monitor_id)
+            })
+        else {
+            return;
+        };
+        let clients = self
+            .clients
+            .iter()
+            .filter(|client| client.workspace.id == id)
+            .collect();
+        Minimap::new(monitor, clients).render(inner, buf);
+    }
+
+    /// Shows the client and workspace tables next to each other, with the
+    /// one that has focus highlighted.
+    fn render_side_by_side(&self, area: Rect, buf: &mut Buffer) {
+        let [client_area, workspace_area] =
+            Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]).areas(area);
+        let pane = |title: &'static str, focused: bool| {
+            let block = Block::bordered().title(title);
+            if focused {
+                return block
+                    .border_set(border::THICK)
+                    .border_style(Style::new().blue());
+            }
+            block
+        };
+
+        let focused = matches!(self.current_table, SelectedTable::Clients);
+        let block = pane(" Clients ", focused);
+        self.client_table.render(block.inner(client_area), buf);
+        block.render(client_area, buf);
+
+        // The pane is too narrow to fit the preview beside the table
+        let block = pane(" Workspaces ", !focused);
+        let [table_area, minimap_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)])
+                .areas(block.inner(workspace_area));
+        self.workspace_table.render(table_area, buf);
+        self.render_minimap(minimap_area, buf);
+        block.render(workspace_area, buf);
+    }
+
     fn border_title(&self) -> Vec<Span<'a>> {
-        let mut lines: Vec<Span> = vec![" Clients ".into(), "|".into(), " Workspaces ".into()];
+        let mut lines: Vec<Span> = vec![
+            " Clients ".into(),
+            "|".into(),
+            " Workspaces ".into(),
+            "|".into(),
+            " Minimized ".into(),
+        ];
 
         match self.current_table {
             SelectedTable::Clients => lines[0] = lines[0].clone().blue(),
             SelectedTable::Workspaces => lines[2] = lines[2].clone().blue(),
+            SelectedTable::Minimized => lines[4] = lines[4].clone().blue(),
         };
 
         lines
     }
 
-    fn table_move_down(&mut self) -> Option<ScreenEvent> {
+    fn table_move(&mut self, motion: Motion) -> Option<ScreenEvent> {
         match self.current_table {
-            SelectedTable::Clients => self.client_table.move_down(),
-            SelectedTable::Workspaces => self.workspace_table.move_down(),
+            SelectedTable::Clients => self.client_table.move_cursor(motion),
+            SelectedTable::Workspaces => {
+                self.workspace_table.move_cursor(motion);
+                if self.filter_clients {
+                    self.rebuild_client_table();
+                }
+            }
+            SelectedTable::Minimized => self.minimized_table.move_cursor(motion),
         };
 
-        None
+        self.peek_selected_client()
     }
 
-    fn table_move_up(&mut self) -> Option<ScreenEvent> {
-        match self.current_table {
-            SelectedTable::Clients => self.client_table.move_up(),
-            SelectedTable::Workspaces => self.workspace_table.move_up(),
+    /// Switches to the tab whose title is at `column`, `row`, returning false
+    /// if there is none there.
+    fn click_tab(&mut self, column: u16, row: u16) -> bool {
+        let area = self.area.get();
+        if row != area.y {
+            return false;
+        }
+        // Titles start after the corner, with a separator between each
+        let mut x = area.x + 1;
+        for (index, span) in self.border_title().iter().enumerate() {
+            let width = span.width() as u16;
+            if index % 2 == 0 && (x..x + width).contains(&column) {
+                self.current_table = match index / 2 {
+                    0 => SelectedTable::Clients,
+                    1 => SelectedTable::Workspaces,
+                    _ => SelectedTable::Minimized,
+                };
+                return true;
+            }
+            x += width;
+        }
+
+        false
+    }
+
+    /// Selects the row at `column`, `row` in whichever table is drawn there,
+    /// returning false if there is none.
+    fn click_table(&mut self, column: u16, row: u16) -> bool {
+        let tables_shown = match self.current_table {
+            SelectedTable::Minimized => return self.minimized_table.click(column, row),
+            _ if self.side_by_side.get() => (true, true),
+            SelectedTable::Clients => (true, false),
+            SelectedTable::Workspaces => (false, true),
         };
+        if tables_shown.0 && self.client_table.click(column, row) {
+            self.current_table = SelectedTable::Clients;
+            return true;
+        }
+        if tables_shown.1 && self.workspace_table.click(column, row) {
+            self.current_table = SelectedTable::Workspaces;
+            if self.filter_clients {
+                self.rebuild_client_table();
+            }
+            return true;
+        }
 
-        None
+        false
+    }
+
+    fn toggle_peek(&mut self) -> Option<ScreenEvent> {
+        self.peek = !self.peek;
+
+        self.peek_selected_client()
+    }
+
+    fn peek_selected_client(&self) -> Option<ScreenEvent> {
+        match self.current_table {
+            SelectedTable::Clients if self.peek => {
+                Some(ScreenEvent::Peek(self.client_table.selected_client()?))
+            }
+            _ => None,
+        }
     }
 
     fn switch_to_selected_workspace(&mut self) -> Option<ScreenEvent> {
-        let (id_option, client_address) = match self.current_table {
+        let (workspace_option, client_address) = match self.current_table {
+            SelectedTable::Clients if self.client_table.selected_group().is_some() => {
+                return self.focus_next_in_group();
+            }
             SelectedTable::Clients => self.client_table.selected_workspace(),
-            SelectedTable::Workspaces => (self.workspace_table.selected_workspace(), None),
+            SelectedTable::Workspaces => {
+                (self.workspace_table.selected().map(|ws| ws.target()), None)
+            }
+            SelectedTable::Minimized => return self.restore_selected_client(),
         };
-        if let Some(id) = id_option {
-            if let Ok(_) = crate::hypr::switch_to_workspace(id, client_address) {
+        if let Some(workspace) = workspace_option {
+            if crate::hypr::switch_to_workspace(&workspace, client_address).is_ok() {
                 return Some(ScreenEvent::Close);
             }
         }
@@ -94,8 +571,20 @@ impl<'a> TableScreen<'_> {
         None
     }
 
-    fn switch_to_workspace(&mut self, id: u32) -> Option<ScreenEvent> {
-        if let Ok(_) = crate::hypr::switch_to_workspace(id, None) {
+    fn switch_to_workspace(&mut self, id: i32) -> Option<ScreenEvent> {
+        if crate::hypr::switch_to_workspace(&id.to_string(), None).is_ok() {
+            return Some(ScreenEvent::Close);
+        }
+
+        None
+    }
+
+    fn pull_selected_client(&mut self) -> Option<ScreenEvent> {
+        let SelectedTable::Clients = self.current_table else {
+            return None;
+        };
+        let client_address = self.client_table.selected_client()?;
+        if hypr::pull_to_active_workspace(&client_address).is_ok() {
             return Some(ScreenEvent::Close);
         }
 
@@ -103,14 +592,152 @@ impl<'a> TableScreen<'_> {
     }
 
     fn send_selected_client_to_workspace(&mut self) -> Option<ScreenEvent> {
-        if let Some(client_address) = self.client_table.selected_client() {
+        if let Some(client) = self.client_table.selected() {
             return Some(ScreenEvent::AddScreen(Box::new(SendWorkspaceScreen::new(
-                client_address,
+                client,
+                self.workspace_columns.clone(),
             ))));
         };
 
         None
     }
+
+    fn open_recent_clients(&mut self) -> Option<ScreenEvent> {
+        if let Ok(mru_screen) = MruScreen::new(self.client_columns.shown.clone(), false) {
+            return Some(ScreenEvent::AddScreen(Box::new(mru_screen)));
+        }
+
+        None
+    }
+
+    /// Lists the clients on the workspace under the cursor in a screen of
+    /// their own.
+    fn open_selected_workspace(&mut self) -> Option<ScreenEvent> {
+        let SelectedTable::Workspaces = self.current_table else {
+            return None;
+        };
+        let workspace = self.workspace_table.selected()?;
+        let workspace_screen = WorkspaceScreen::new(
+            workspace,
+            self.client_columns.clone(),
+            self.workspace_columns.clone(),
+        );
+        if let Ok(workspace_screen) = workspace_screen {
+            return Some(ScreenEvent::AddScreen(Box::new(workspace_screen)));
+        }
+
+        None
+    }
+
+    fn open_overview(&mut self) -> Option<ScreenEvent> {
+        if let Ok(overview_screen) = OverviewScreen::new() {
+            return Some(ScreenEvent::AddScreen(Box::new(overview_screen)));
+        }
+
+        None
+    }
+
+    fn handle_prefixed_key(&mut self, prefix: char, key_event: KeyEvent) -> Option<ScreenEvent> {
+        if prefix == 'i' {
+            let direction = match key_event.code {
+                KeyCode::Char('h') | KeyCode::Left => 'l',
+                KeyCode::Char('j') | KeyCode::Down => 'd',
+                KeyCode::Char('k') | KeyCode::Up => 'u',
+                KeyCode::Char('l') | KeyCode::Right => 'r',
+                _ => return None,
+            };
+            return self
+                .group_action(|address, own| hypr::move_into_group(address, direction, own));
+        }
+
+        let KeyCode::Char(letter) = key_event.code else {
+            return None;
+        };
+        match prefix {
+            'm' => {
+                let SelectedTable::Clients = self.current_table else {
+                    return None;
+                };
+                if let Err(err) = marks::set(letter, self.client_table.selected()?) {
+                    return Some(ScreenEvent::Error(err.context("Setting the mark")));
+                }
+                None
+            }
+            '\'' => match marks::jump(letter) {
+                Ok(()) => Some(ScreenEvent::Close),
+                Err(err) => Some(ScreenEvent::Error(err)),
+            },
+            _ => None,
+        }
+    }
+
+    fn open_scratchpads(&mut self) -> Option<ScreenEvent> {
+        let scratchpad_screen = ScratchpadScreen::new().ok()?;
+        Some(ScreenEvent::AddScreen(Box::new(scratchpad_screen)))
+    }
+
+    fn send_selected_client_to_scratchpad(&mut self) -> Option<ScreenEvent> {
+        let scratchpad_screen = ScratchpadScreen::sending(self.client_table.selected()?).ok()?;
+        Some(ScreenEvent::AddScreen(Box::new(scratchpad_screen)))
+    }
+
+    fn minimize_selected_client(&mut self) -> Option<ScreenEvent> {
+        let SelectedTable::Clients = self.current_table else {
+            return None;
+        };
+        let client = self.client_table.selected()?;
+        minimize::minimize(client).ok()?;
+
+        Some(ScreenEvent::Record(Operation::new(
+            format!("Minimize {}", client.class),
+            vec![Change::Minimized {
+                address: client.address.clone(),
+                from: client.workspace.target(),
+            }],
+        )))
+    }
+
+    fn restore_selected_client(&mut self) -> Option<ScreenEvent> {
+        let client = self.minimized_table.selected()?;
+        let origin = minimize::restore(&client.address, self.active_workspace).ok()?;
+
+        Some(ScreenEvent::Record(Operation::new(
+            format!("Restore {} to {}", client.class, origin.name),
+            vec![Change::Restored {
+                address: client.address.clone(),
+                origin,
+            }],
+        )))
+    }
+
+    /// Runs a group dispatcher on the selected client and reloads the tables,
+    /// since group changes can reorder them.
+    fn group_action(
+        &mut self,
+        action: impl FnOnce(&str, Option<&str>) -> Result<()>,
+    ) -> Option<ScreenEvent> {
+        let SelectedTable::Clients = self.current_table else {
+            return None;
+        };
+        let client_address = self.client_table.selected_client()?;
+        if action(&client_address, self.own_address.as_deref()).is_ok() {
+            self.refresh();
+            self.client_table.select_client(&client_address);
+        }
+
+        None
+    }
+
+    fn change_group_active(&mut self, forward: bool) -> Option<ScreenEvent> {
+        let clients = self.clients.clone();
+        self.group_action(|address, own| hypr::change_group_active(address, &clients, forward, own))
+    }
+
+    fn organize(&mut self) -> Option<ScreenEvent> {
+        Some(ScreenEvent::AddScreen(Box::new(OrganizeScreen::new(
+            self.config.rules.clone(),
+        ))))
+    }
 }
 
 impl WidgetRef for TableScreen<'_> {
@@ -121,59 +748,169 @@ impl WidgetRef for TableScreen<'_> {
             " Quit ".into(),
             "<Q> ".blue().bold(),
         ]);
-        Block::bordered()
+        let mut block = Block::bordered()
             .title_top(self.border_title())
             .title_bottom(instructions.centered())
-            .border_set(border::THICK)
-            .render(area, buf);
+            .border_set(border::THICK);
+        if self.peek {
+            block = block.title_top(Line::from(" Peek ".green()).right_aligned());
+        }
+        if self.filter_clients {
+            block = block.title_top(Line::from(" Filter ".green()).right_aligned());
+        }
+        let group_by = match self.group_by {
+            GroupBy::None => None,
+            GroupBy::Workspace => Some(" By workspace "),
+            GroupBy::Monitor => Some(" By monitor "),
+            GroupBy::Class => Some(" By class "),
+        };
+        if let Some(group_by) = group_by {
+            block = block.title_top(Line::from(group_by.green()).right_aligned());
+        }
+        block.render(area, buf);
+        self.area.set(area);
 
         let area = area.inner(Margin {
             horizontal: 1,
             vertical: 1,
         });
 
+        self.side_by_side.set(false);
         match self.current_table {
+            SelectedTable::Clients | SelectedTable::Workspaces
+                if area.width >= SIDE_BY_SIDE_MIN_WIDTH =>
+            {
+                self.side_by_side.set(true);
+                self.render_side_by_side(area, buf)
+            }
             SelectedTable::Clients => self.client_table.render(area, buf),
-            SelectedTable::Workspaces => self.workspace_table.render(area, buf),
+            SelectedTable::Workspaces => self.render_workspaces(area, buf),
+            SelectedTable::Minimized => self.minimized_table.render(area, buf),
         };
     }
 }
 
 impl ScreenWidget for TableScreen<'_> {
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
-        match key_event.code {
-            KeyCode::Down => self.table_move_down(),
-            KeyCode::Char('j') => self.table_move_down(),
+        if let Some(prefix) = self.pending_prefix.take() {
+            return self.handle_prefixed_key(prefix, key_event);
+        }
 
-            KeyCode::Up => self.table_move_up(),
-            KeyCode::Char('k') => self.table_move_up(),
+        if let Some(digit) = self.motion_keys.workspace_digit(key_event) {
+            return self.switch_to_workspace(digit.to_digit(10)? as i32);
+        }
+        if self.motion_keys.handle_count(key_event) {
+            return None;
+        }
+        if let Some(motion) = self.motion_keys.motion(key_event) {
+            return self.table_move(motion);
+        }
 
+        match key_event.code {
             KeyCode::Enter => self.switch_to_selected_workspace(),
-            KeyCode::Char('0') => self.switch_to_workspace(0),
-            KeyCode::Char('1') => self.switch_to_workspace(1),
-            KeyCode::Char('2') => self.switch_to_workspace(2),
-            KeyCode::Char('3') => self.switch_to_workspace(3),
-            KeyCode::Char('4') => self.switch_to_workspace(4),
-            KeyCode::Char('5') => self.switch_to_workspace(5),
-            KeyCode::Char('6') => self.switch_to_workspace(6),
-            KeyCode::Char('7') => self.switch_to_workspace(7),
-            KeyCode::Char('8') => self.switch_to_workspace(8),
-            KeyCode::Char('9') => self.switch_to_workspace(9),
+            KeyCode::Char(' ') => self.focus_active_in_group(),
+            KeyCode::Right => self.open_selected_workspace(),
+            KeyCode::Char('l') => self.open_selected_workspace(),
 
             KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+            // This is the first screen, so popping it quits and undoes any peeking
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
             KeyCode::Char('s') => self.send_selected_client_to_workspace(),
+            KeyCode::Char('b') => self.pull_selected_client(),
+            KeyCode::Char('S') => self.send_selected_client_to_scratchpad(),
+            KeyCode::Char('x') => self.open_scratchpads(),
+            KeyCode::Char('z') => self.minimize_selected_client(),
+            KeyCode::Char('r') => self.open_recent_clients(),
+            KeyCode::Char('w') => self.open_overview(),
+            KeyCode::Char('C') => Some(ScreenEvent::AddScreen(Box::new(ConsoleScreen::new()))),
+            KeyCode::Char('f') => self.toggle_client_filter(),
+            KeyCode::Char('c') => self.cycle_sort(),
+            KeyCode::Char('v') => self.cycle_group_by(),
+            KeyCode::Char('p') => self.toggle_peek(),
+            KeyCode::Char('o') => self.organize(),
+            KeyCode::Char('u') => Some(ScreenEvent::Undo(1)),
+            KeyCode::Char('U') => Some(ScreenEvent::ShowHistory),
+            KeyCode::Char('t') => self.group_action(hypr::toggle_group),
+            KeyCode::Char('O') => self.group_action(|address, _| hypr::move_out_of_group(address)),
+            KeyCode::Char('<') => self.change_group_active(false),
+            KeyCode::Char('>') => self.change_group_active(true),
+            KeyCode::Char(prefix @ ('m' | '\'' | 'i')) => {
+                self.pending_prefix = Some(prefix);
+                None
+            }
 
             KeyCode::Tab => self.next_border_screen(),
+            KeyCode::BackTab => self.previous_border_screen(),
             _ => None,
         }
     }
 
+    fn selection(&self) -> Selection {
+        match self.current_table {
+            SelectedTable::Clients => self.client_table.selection(),
+            SelectedTable::Workspaces => Selection {
+                workspace: self.workspace_table.selected().map(|ws| ws.target()),
+                ..Selection::default()
+            },
+            SelectedTable::Minimized => self.minimized_table.selection(),
+        }
+    }
+
     fn refresh(&mut self) {
-        if let Ok(hypr) = Hypr::new().context("Connecting to Hyprland") {
-            self.client_table = ClientTable::new(hypr.clients);
-            self.workspace_table = WorkspaceTable::new(hypr.workspaces);
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            // Preselecting is only for when wmt opens, so keep the cursors put
+            let selected_client = self.client_table.selected_client();
+            let selected_workspace = self.workspace_table.selected_workspace();
+            self.active_workspace = hypr.active_workspace;
+            self.own_address = hypr.own_address.clone();
+            self.monitors = hypr.monitors.clone();
+            self.clients = hypr.clients.clone();
+            self.active_client = hypr.active_client();
+            self.minimized_table = Self::build_minimized_table(&hypr, &self.client_columns);
+            (self.client_table, self.workspace_table) = Self::build_tables(
+                hypr,
+                self.config.preselect,
+                &self.client_columns,
+                &self.workspace_columns,
+            );
+            if let Some(id) = selected_workspace {
+                self.workspace_table.select_workspace(id);
+            }
+            if let Some(address) = selected_client {
+                self.client_table.select_client(&address);
+            }
+            if self.filter_clients || self.group_by != GroupBy::None {
+                self.rebuild_client_table();
+            }
+        }
+    }
+
+    /// A click selects a row or switches tabs, a double click does what
+    /// Enter would and the wheel moves the cursor.
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Option<ScreenEvent> {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.click_tab(column, row) {
+                    return None;
+                }
+                if !self.click_table(column, row) {
+                    return None;
+                }
+                if self.clicks.is_double(column, row) {
+                    return self.switch_to_selected_workspace();
+                }
+                self.peek_selected_client()
+            }
+            MouseEventKind::ScrollDown => self.table_move(Motion::Down(SCROLL_ROWS)),
+            MouseEventKind::ScrollUp => self.table_move(Motion::Up(SCROLL_ROWS)),
+            _ => None,
         }
     }
+
+    fn captures_input(&self) -> bool {
+        self.pending_prefix.is_some()
+    }
 }
 
 impl Screen for TableScreen<'_> {}
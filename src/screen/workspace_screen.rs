@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Widget, WidgetRef},
+};
+
+use crate::{
+    app::{
+        client_table::ClientTable,
+        columns::{ClientColumn, Columns, WorkspaceColumn},
+        command_line::Selection,
+        mouse::{Clicks, SCROLL_ROWS},
+        scrolling::{Motion, MotionKeys},
+    },
+    hypr::{self, Hypr, Workspace},
+};
+
+use super::{
+    help_screen::HelpScreen, send_workspace_screen::SendWorkspaceScreen, Screen, ScreenEvent,
+    ScreenWidget,
+};
+
+/// The clients on a single workspace, opened from the workspace table.
+pub struct WorkspaceScreen<'a> {
+    workspace_id: i32,
+    workspace_name: String,
+    /// See [`Workspace::target`].
+    workspace_target: String,
+    client_table: ClientTable<'a>,
+    columns: Columns<ClientColumn>,
+    /// Passed on to the send to workspace screen.
+    workspace_columns: Columns<WorkspaceColumn>,
+    own_address: Option<String>,
+    motion_keys: MotionKeys,
+    clicks: Clicks,
+    /// Set by `c` until `y` confirms closing the selected window, or any
+    /// other key cancels it.
+    confirm_close: bool,
+}
+
+impl<'a> WorkspaceScreen<'_> {
+    pub fn new(
+        workspace: &Workspace,
+        columns: Columns<ClientColumn>,
+        workspace_columns: Columns<WorkspaceColumn>,
+    ) -> Result<WorkspaceScreen<'a>> {
+        let hypr = Hypr::without_own_window().context("Connecting to Hyprland")?;
+        let own_address = hypr.own_address.clone();
+        Ok(WorkspaceScreen {
+            workspace_id: workspace.id,
+            workspace_name: workspace.name.clone(),
+            workspace_target: workspace.target(),
+            client_table: Self::build_table(hypr, workspace.id, &columns),
+            columns,
+            workspace_columns,
+            own_address,
+            motion_keys: MotionKeys::default(),
+            clicks: Clicks::default(),
+            confirm_close: false,
+        })
+    }
+
+    fn build_table(
+        hypr: Hypr,
+        workspace_id: i32,
+        columns: &Columns<ClientColumn>,
+    ) -> ClientTable<'a> {
+        let active_client = hypr.active_client();
+        let clients = hypr
+            .clients
+            .into_iter()
+            .filter(|client| client.workspace.id == workspace_id)
+            .collect();
+        ClientTable::nested(clients, active_client, columns)
+    }
+
+    fn table_move(&mut self, motion: Motion) -> Option<ScreenEvent> {
+        self.client_table.move_cursor(motion);
+
+        None
+    }
+
+    fn focus_selected_client(&mut self) -> Option<ScreenEvent> {
+        let client_address = self.client_table.selected_client()?;
+        if hypr::switch_to_workspace(&self.workspace_target, Some(client_address)).is_ok() {
+            return Some(ScreenEvent::Close);
+        }
+
+        None
+    }
+
+    fn send_selected_client(&mut self) -> Option<ScreenEvent> {
+        let client = self.client_table.selected()?;
+        Some(ScreenEvent::AddScreen(Box::new(SendWorkspaceScreen::new(
+            client,
+            self.workspace_columns.clone(),
+        ))))
+    }
+
+    fn ask_to_close(&mut self) -> Option<ScreenEvent> {
+        self.confirm_close = self.client_table.selected().is_some();
+
+        None
+    }
+
+    fn handle_confirm_close_key(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        self.confirm_close = false;
+        match key_event.code {
+            KeyCode::Char('y') => self.client_action(|address, _| hypr::close_client(address)),
+            _ => None,
+        }
+    }
+
+    /// Runs `action` on the selected client, then refreshes to show what it
+    /// changed while keeping the cursor on the same client.
+    fn client_action(
+        &mut self,
+        action: impl FnOnce(&str, Option<&str>) -> Result<()>,
+    ) -> Option<ScreenEvent> {
+        let client_address = self.client_table.selected_client()?;
+        if action(&client_address, self.own_address.as_deref()).is_ok() {
+            self.refresh();
+            self.client_table.select_client(&client_address);
+        }
+
+        None
+    }
+}
+
+impl WidgetRef for WorkspaceScreen<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let instructions = Line::from(vec![
+            " Back ".into(),
+            "<h>".blue().bold(),
+            " Help ".into(),
+            "<?>".blue().bold(),
+            " Quit ".into(),
+            "<Q> ".blue().bold(),
+        ]);
+        Block::bordered()
+            .title_top(format!(" Workspace {} ", self.workspace_name))
+            .title_bottom(instructions.centered())
+            .border_set(border::THICK)
+            .render(area, buf);
+
+        let area = area.inner(Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        if !self.confirm_close {
+            self.client_table.render(area, buf);
+            return;
+        }
+        let [table_area, prompt_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+        self.client_table.render(table_area, buf);
+        if let Some(client) = self.client_table.selected() {
+            Line::from(vec![
+                format!("Close {}? ", client.class).into(),
+                "<y>".blue().bold(),
+            ])
+            .render(prompt_area, buf);
+        }
+    }
+}
+
+impl ScreenWidget for WorkspaceScreen<'_> {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<ScreenEvent> {
+        if self.confirm_close {
+            return self.handle_confirm_close_key(key_event);
+        }
+        if self.motion_keys.handle_count(key_event) {
+            return None;
+        }
+        if let Some(motion) = self.motion_keys.motion(key_event) {
+            return self.table_move(motion);
+        }
+
+        match key_event.code {
+            KeyCode::Enter => self.focus_selected_client(),
+            KeyCode::Char('s') => self.send_selected_client(),
+            // Not Ctrl-c, which is easy to press out of habit to quit
+            KeyCode::Char('c') if key_event.modifiers.is_empty() => self.ask_to_close(),
+            KeyCode::Char('v') => self.client_action(|address, _| hypr::toggle_floating(address)),
+            KeyCode::Char('F') => self.client_action(hypr::toggle_fullscreen),
+
+            KeyCode::Left => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('h') => Some(ScreenEvent::PopScreen),
+            KeyCode::Esc => Some(ScreenEvent::PopScreen),
+            KeyCode::Char('?') => Some(ScreenEvent::AddScreen(Box::new(HelpScreen::new()))),
+
+            _ => None,
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Option<ScreenEvent> {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.client_table.click(column, row) && self.clicks.is_double(column, row) {
+                    return self.focus_selected_client();
+                }
+                None
+            }
+            MouseEventKind::ScrollDown => self.table_move(Motion::Down(SCROLL_ROWS)),
+            MouseEventKind::ScrollUp => self.table_move(Motion::Up(SCROLL_ROWS)),
+            _ => None,
+        }
+    }
+
+    fn selection(&self) -> Selection {
+        self.client_table.selection()
+    }
+
+    fn captures_input(&self) -> bool {
+        self.confirm_close
+    }
+
+    fn refresh(&mut self) {
+        if let Ok(hypr) = Hypr::without_own_window().context("Connecting to Hyprland") {
+            self.own_address = hypr.own_address.clone();
+            self.client_table = Self::build_table(hypr, self.workspace_id, &self.columns);
+        }
+    }
+}
+impl Screen for WorkspaceScreen<'_> {}
@@ -0,0 +1,237 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::hypr::{self, Client, ClientWorkspace, Geometry, Hypr, Placement};
+use crate::state::state_dir;
+
+/// A snapshot of which workspace every window was on.
+#[derive(Serialize, Deserialize, Debug)]
+struct Session {
+    clients: Vec<SessionClient>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SessionClient {
+    class: String,
+    initial_class: String,
+    title: String,
+    workspace: ClientWorkspace,
+    floating: Option<Geometry>,
+}
+
+impl From<Client> for SessionClient {
+    fn from(client: Client) -> SessionClient {
+        let floating = client.floating.then_some(Geometry {
+            at: client.at,
+            size: client.size,
+        });
+        SessionClient {
+            class: client.class,
+            initial_class: client.initial_class,
+            title: client.title,
+            workspace: client.workspace,
+            floating,
+        }
+    }
+}
+
+impl SessionClient {
+    /// How likely it is that `client` is the window this entry was saved
+    /// from. Window addresses change when apps restart, so this goes by class
+    /// and title instead, and 0 means it can't be the same window.
+    fn match_score(&self, client: &Client) -> u32 {
+        let mut score = 0;
+        if client.initial_class == self.initial_class {
+            score += 2;
+        }
+        if client.class == self.class {
+            score += 2;
+        }
+        if score == 0 {
+            return 0;
+        }
+
+        // Every title contains an empty one, so that says nothing
+        let titles_set = !client.title.is_empty() && !self.title.is_empty();
+        if client.title == self.title {
+            score += 4;
+        } else if titles_set
+            && (client.title.contains(&self.title) || self.title.contains(&client.title))
+        {
+            score += 1;
+        }
+
+        score
+    }
+}
+
+pub struct RestoreSummary {
+    pub restored: usize,
+    pub saved: usize,
+}
+
+/// Saves the workspace and floating geometry of every client, returning how
+/// many were saved.
+pub fn save(name: &str) -> Result<usize> {
+    let path = session_path(name)?;
+    let hypr = Hypr::new().context("Connecting to Hyprland")?;
+    let session = Session {
+        clients: hypr.clients.into_iter().map(SessionClient::from).collect(),
+    };
+
+    let dir = path.parent().context("Session path has no parent")?;
+    fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+    let contents = serde_json::to_string_pretty(&session)?;
+    fs::write(&path, contents).with_context(|| format!("Writing {}", path.display()))?;
+
+    Ok(session.clients.len())
+}
+
+/// Moves the current clients back to where the best matching saved entries
+/// were, all in one batch.
+pub fn restore(name: &str) -> Result<RestoreSummary> {
+    let path = session_path(name)?;
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))?;
+    let session: Session =
+        serde_json::from_str(&contents).with_context(|| format!("Parsing {}", path.display()))?;
+    let hypr = Hypr::new().context("Connecting to Hyprland")?;
+
+    let placements: Vec<Placement> = match_clients(&session.clients, &hypr.clients)
+        .into_iter()
+        .map(|(saved_index, client_index)| {
+            let saved = &session.clients[saved_index];
+            Placement {
+                address: hypr.clients[client_index].address.clone(),
+                workspace: saved.workspace.target(),
+                floating: saved.floating,
+            }
+        })
+        .collect();
+
+    hypr::place_clients(&placements).context("Moving clients")?;
+
+    Ok(RestoreSummary {
+        restored: placements.len(),
+        saved: session.clients.len(),
+    })
+}
+
+/// Pairs saved entries with current clients, as indices into each. Each is
+/// used at most once, and the strongest matches are handed out first so a
+/// window with the exact title isn't claimed by a weaker candidate.
+fn match_clients(saved: &[SessionClient], clients: &[Client]) -> Vec<(usize, usize)> {
+    let mut candidates = Vec::new();
+    for (saved_index, saved) in saved.iter().enumerate() {
+        for (client_index, client) in clients.iter().enumerate() {
+            let score = saved.match_score(client);
+            if score > 0 {
+                candidates.push((score, saved_index, client_index));
+            }
+        }
+    }
+    candidates.sort_by_key(|(score, _, _)| Reverse(*score));
+
+    let mut saved_taken = vec![false; saved.len()];
+    let mut client_taken = vec![false; clients.len()];
+    let mut pairs = Vec::new();
+    for (_, saved_index, client_index) in candidates {
+        if saved_taken[saved_index] || client_taken[client_index] {
+            continue;
+        }
+        saved_taken[saved_index] = true;
+        client_taken[client_index] = true;
+        pairs.push((saved_index, client_index));
+    }
+
+    pairs
+}
+
+fn session_path(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains('/') {
+        bail!("Invalid session name: {:?}", name);
+    }
+
+    Ok(state_dir()?.join("sessions").join(format!("{}.json", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(class: &str, title: &str) -> SessionClient {
+        SessionClient::from(Client::for_test("0x0", class, title))
+    }
+
+    #[test]
+    fn class_and_title_add_up() {
+        let entry = saved("kitty", "vim notes.md");
+        let mut renamed = Client::for_test("0x1", "kitty", "vim todo.md");
+        renamed.initial_class = "other".to_string();
+
+        assert_eq!(
+            entry.match_score(&Client::for_test("0x1", "kitty", "vim notes.md")),
+            8
+        );
+        assert_eq!(
+            entry.match_score(&Client::for_test("0x1", "kitty", "vim")),
+            5
+        );
+        assert_eq!(
+            entry.match_score(&Client::for_test("0x1", "kitty", "htop")),
+            4
+        );
+        assert_eq!(entry.match_score(&renamed), 2);
+    }
+
+    #[test]
+    fn other_classes_never_match() {
+        let entry = saved("kitty", "vim notes.md");
+        assert_eq!(
+            entry.match_score(&Client::for_test("0x1", "firefox", "vim notes.md")),
+            0
+        );
+    }
+
+    #[test]
+    fn empty_titles_are_not_partial_matches() {
+        let untitled = Client::for_test("0x1", "kitty", "");
+        assert_eq!(saved("kitty", "vim").match_score(&untitled), 4);
+        assert_eq!(
+            saved("kitty", "").match_score(&Client::for_test("0x1", "kitty", "vim")),
+            4
+        );
+        assert_eq!(saved("kitty", "").match_score(&untitled), 8);
+    }
+
+    #[test]
+    fn exact_titles_are_matched_first() {
+        let saved = [saved("kitty", "vim"), saved("kitty", "vim notes.md")];
+        let clients = [
+            Client::for_test("0x1", "kitty", "vim notes.md"),
+            Client::for_test("0x2", "kitty", "vim"),
+        ];
+
+        let mut pairs = match_clients(&saved, &clients);
+        pairs.sort();
+        assert_eq!(pairs, [(0, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn each_window_is_matched_once() {
+        let saved = [
+            saved("kitty", "a"),
+            saved("kitty", "b"),
+            saved("kitty", "c"),
+        ];
+        let clients = [
+            Client::for_test("0x1", "kitty", "b"),
+            Client::for_test("0x2", "firefox", "a"),
+        ];
+
+        assert_eq!(match_clients(&saved, &clients), [(1, 0)]);
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::app::columns::{ClientColumn, Sort, WorkspaceColumn};
+use crate::hypr::ClientWorkspace;
+
+/// Data wmt keeps between runs in `$XDG_STATE_HOME/wmt/state.json`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct State {
+    pub marks: BTreeMap<char, Mark>,
+    /// Minimized windows, oldest first.
+    pub minimized: Vec<Minimized>,
+    /// How the client and workspace tables were last sorted.
+    pub client_sort: Option<Sort<ClientColumn>>,
+    pub workspace_sort: Option<Sort<WorkspaceColumn>>,
+    /// Commands run from the `:` prompt, oldest first.
+    pub command_history: Vec<String>,
+    /// Requests sent from the console screen, oldest first.
+    pub console_history: Vec<String>,
+}
+
+/// A window remembered under a letter. Addresses don't survive restarts, so
+/// the class and initial title are kept to find the window again.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Mark {
+    pub address: String,
+    pub class: String,
+    pub initial_title: String,
+}
+
+/// A window hidden in `special:minimized` and the workspace it came from.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Minimized {
+    pub address: String,
+    pub origin: ClientWorkspace,
+}
+
+impl State {
+    pub fn load() -> Result<State> {
+        let path = state_dir()?.join("state.json");
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(State::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Reading {}", path.display()));
+            }
+        };
+
+        serde_json::from_str(&contents).with_context(|| format!("Parsing {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let dir = state_dir()?;
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        let path = dir.join("state.json");
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(&path, contents).with_context(|| format!("Writing {}", path.display()))
+    }
+}
+
+/// Where wmt keeps data that should outlive a single run,
+/// `$XDG_STATE_HOME/wmt` or `~/.local/state/wmt`.
+pub fn state_dir() -> Result<PathBuf> {
+    let base = match env::var("XDG_STATE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var("HOME").context("Failed to find $HOME")?).join(".local/state"),
+    };
+
+    Ok(base.join("wmt"))
+}